@@ -0,0 +1,87 @@
+use super::gjk::support;
+use crate::geometry::PointF;
+
+const MAX_ITERATIONS: usize = 64;
+const TOLERANCE: f64 = 1e-9;
+
+/// Expands the simplex enclosing the origin returned by GJK into the Minkowski difference `a - b`
+/// until it reaches the edge closest to the origin. Returns the unit normal of that edge, pointing
+/// from `a` toward `b`, and its distance to the origin, which is the penetration depth.
+///
+/// Returns `None` when the shapes only touch.
+pub(crate) fn penetration(
+    a: &[PointF],
+    b: &[PointF],
+    simplex: Vec<PointF>,
+) -> Option<(PointF, f64)> {
+    let mut polytope = complete_simplex(a, b, simplex)?;
+
+    let mut best = (PointF::default(), 0.0);
+    for _ in 0..MAX_ITERATIONS {
+        let (index, normal, distance) = (0..polytope.len())
+            .filter_map(|i| {
+                let (p, q) = (polytope[i], polytope[(i + 1) % polytope.len()]);
+                let edge = q - p;
+                let length = edge.length();
+                if length == 0.0 {
+                    return None;
+                }
+                // The polytope winds clockwise on screen, so this normal points outward.
+                let normal = PointF::new(edge.y, -edge.x) / length;
+                Some((i, normal, normal.dot(p)))
+            })
+            .min_by(|(_, _, d), (_, _, e)| d.total_cmp(e))?;
+        best = (normal, distance);
+
+        let point = support(a, b, normal);
+        if point.dot(normal) - distance <= TOLERANCE * distance.max(1.0) {
+            break;
+        }
+        polytope.insert(index + 1, point);
+    }
+
+    (best.1 > TOLERANCE).then_some(best)
+}
+
+// GJK may stop on a point or a segment when the origin lies on it. Grow such simplices into a
+// triangle with a positive area, failing when the Minkowski difference itself is flat.
+fn complete_simplex(a: &[PointF], b: &[PointF], mut simplex: Vec<PointF>) -> Option<Vec<PointF>> {
+    if simplex.len() == 1 {
+        let directions = [
+            PointF::new(1.0, 0.0),
+            PointF::new(-1.0, 0.0),
+            PointF::new(0.0, 1.0),
+            PointF::new(0.0, -1.0),
+        ];
+        let point = directions
+            .into_iter()
+            .map(|direction| support(a, b, direction))
+            .find(|&point| point != simplex[0])?;
+        simplex.push(point);
+    }
+    if simplex.len() == 2 {
+        let edge = simplex[1] - simplex[0];
+        let normal = PointF::new(-edge.y, edge.x);
+        let point = [normal, -normal]
+            .into_iter()
+            .map(|direction| support(a, b, direction))
+            .max_by(|p, q| {
+                (p.dot(normal) - simplex[0].dot(normal))
+                    .abs()
+                    .total_cmp(&(q.dot(normal) - simplex[0].dot(normal)).abs())
+            })?;
+        simplex.push(point);
+    }
+
+    let [p, q, r] = simplex[..] else {
+        return None;
+    };
+    let area = (q - p).cross(r - p);
+    if area.abs() <= TOLERANCE {
+        None
+    } else if area > 0.0 {
+        Some(simplex)
+    } else {
+        Some(vec![p, r, q])
+    }
+}
@@ -0,0 +1,219 @@
+use crate::geometry::PointF;
+
+const MAX_ITERATIONS: usize = 64;
+const TOLERANCE: f64 = 1e-9;
+
+pub(crate) fn support(a: &[PointF], b: &[PointF], direction: PointF) -> PointF {
+    farthest(a, direction) - farthest(b, -direction)
+}
+
+pub(crate) fn farthest(points: &[PointF], direction: PointF) -> PointF {
+    points
+        .iter()
+        .copied()
+        .max_by(|p, q| p.dot(direction).total_cmp(&q.dot(direction)))
+        .unwrap()
+}
+
+// Perpendicular to the segment, pointing toward the origin seen from `a`.
+fn perpendicular_toward_origin(a: PointF, b: PointF) -> PointF {
+    let edge = b - a;
+    let perpendicular = PointF::new(-edge.y, edge.x);
+    if perpendicular.dot(-a) < 0.0 {
+        -perpendicular
+    } else {
+        perpendicular
+    }
+}
+
+enum Outcome {
+    Enclosed(Vec<PointF>),
+    Separated,
+    // The iterations ran out, which happens when the shapes nearly touch.
+    Undecided,
+}
+
+/// Runs GJK on the Minkowski difference `a - b` of two convex point sets. Returns the final simplex
+/// when it encloses the origin, i.e. when the shapes overlap or touch, and `None` otherwise or
+/// when GJK doesn't converge.
+pub(crate) fn intersection(a: &[PointF], b: &[PointF]) -> Option<Vec<PointF>> {
+    intersection_within(a, b, MAX_ITERATIONS)
+}
+
+/// Whether two convex point sets overlap or touch, falling back to their [`distance`] when GJK
+/// doesn't converge.
+pub(crate) fn intersects(a: &[PointF], b: &[PointF]) -> bool {
+    intersects_within(a, b, MAX_ITERATIONS)
+}
+
+fn intersection_within(a: &[PointF], b: &[PointF], max_iterations: usize) -> Option<Vec<PointF>> {
+    match enclose_origin(a, b, max_iterations) {
+        Outcome::Enclosed(simplex) => Some(simplex),
+        Outcome::Separated | Outcome::Undecided => None,
+    }
+}
+
+fn intersects_within(a: &[PointF], b: &[PointF], max_iterations: usize) -> bool {
+    match enclose_origin(a, b, max_iterations) {
+        Outcome::Enclosed(_) => true,
+        Outcome::Separated => false,
+        Outcome::Undecided => distance(a, b) <= TOLERANCE,
+    }
+}
+
+fn enclose_origin(a: &[PointF], b: &[PointF], max_iterations: usize) -> Outcome {
+    let mut direction = PointF::new(1.0, 0.0);
+    let mut simplex = vec![support(a, b, direction)];
+    direction = -simplex[0];
+
+    for _ in 0..max_iterations {
+        if direction.dot(direction) < TOLERANCE * TOLERANCE {
+            return Outcome::Enclosed(simplex);
+        }
+
+        let point = support(a, b, direction);
+        if point.dot(direction) < 0.0 {
+            return Outcome::Separated;
+        }
+        simplex.push(point);
+
+        match simplex[..] {
+            [b, a] => {
+                let ab = b - a;
+                if ab.dot(-a) > 0.0 {
+                    if ab.cross(-a) == 0.0 {
+                        return Outcome::Enclosed(simplex);
+                    }
+                    direction = perpendicular_toward_origin(a, b);
+                } else {
+                    simplex = vec![a];
+                    direction = -a;
+                }
+            }
+            [c, b, a] => {
+                // The origin lies beyond the edge when it is on the other side of it than the
+                // third point.
+                let outside_ab = (b - a).cross(-a) * (b - a).cross(c - a) < 0.0;
+                let outside_ac = (c - a).cross(-a) * (c - a).cross(b - a) < 0.0;
+                if outside_ab {
+                    simplex = vec![b, a];
+                    direction = perpendicular_toward_origin(a, b);
+                } else if outside_ac {
+                    simplex = vec![c, a];
+                    direction = perpendicular_toward_origin(a, c);
+                } else {
+                    return Outcome::Enclosed(simplex);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    Outcome::Undecided
+}
+
+/// Distance between two convex point sets, `0.0` when they overlap.
+pub(crate) fn distance(a: &[PointF], b: &[PointF]) -> f64 {
+    let mut simplex = vec![support(a, b, PointF::new(1.0, 0.0))];
+    let mut closest = simplex[0];
+
+    for _ in 0..MAX_ITERATIONS {
+        let squared = closest.dot(closest);
+        if squared < TOLERANCE * TOLERANCE {
+            return 0.0;
+        }
+
+        let point = support(a, b, -closest);
+        if squared - closest.dot(point) <= TOLERANCE * squared.max(1.0) {
+            break;
+        }
+        simplex.push(point);
+
+        match closest_on_simplex(&simplex) {
+            Some((reduced, point)) => {
+                simplex = reduced;
+                closest = point;
+            }
+            None => return 0.0,
+        }
+    }
+
+    closest.length()
+}
+
+// Closest point to the origin on the simplex, along with the smallest sub-simplex holding it.
+// Returns `None` when a triangle simplex encloses the origin.
+fn closest_on_simplex(simplex: &[PointF]) -> Option<(Vec<PointF>, PointF)> {
+    match *simplex {
+        [a, b] => Some(closest_on_segment(a, b)),
+        [a, b, c] => {
+            let sides = [(b - a).cross(-a), (c - b).cross(-b), (a - c).cross(-c)];
+            if sides.iter().all(|&side| side >= 0.0) || sides.iter().all(|&side| side <= 0.0) {
+                return None;
+            }
+            [
+                closest_on_segment(a, b),
+                closest_on_segment(b, c),
+                closest_on_segment(c, a),
+            ]
+            .into_iter()
+            .min_by(|(_, p), (_, q)| p.dot(*p).total_cmp(&q.dot(*q)))
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn closest_on_segment(a: PointF, b: PointF) -> (Vec<PointF>, PointF) {
+    let ab = b - a;
+    let length = ab.dot(ab);
+    if length == 0.0 {
+        return (vec![a], a);
+    }
+
+    let t = (-a).dot(ab) / length;
+    if t <= 0.0 {
+        (vec![a], a)
+    } else if t >= 1.0 {
+        (vec![b], b)
+    } else {
+        (vec![a, b], a + ab * t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f64, y: f64) -> Vec<PointF> {
+        [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .iter()
+            .map(|&(dx, dy)| PointF::new(x + dx, y + dy))
+            .collect()
+    }
+
+    #[test]
+    fn test_touching() {
+        let a = square(0.0, 0.0);
+        for (b, touching) in [
+            (square(1.0, 0.5), true),
+            (square(1.0, 1.0), true),
+            (square(1.0 + 1e-12, 0.5), false),
+        ] {
+            assert_eq!(intersects(&a, &b), touching);
+            assert_eq!(intersection(&a, &b).is_some(), touching);
+        }
+    }
+
+    #[test]
+    fn test_undecided() {
+        // Out of iterations, touching squares still intersect but give no simplex to EPA
+        let (a, b) = (square(0.0, 0.0), square(1.0, 0.5));
+        assert!(matches!(enclose_origin(&a, &b, 1), Outcome::Undecided));
+        assert!(intersection_within(&a, &b, 1).is_none());
+        assert!(intersects_within(&a, &b, 1));
+
+        let apart = square(1.0 + 1e-6, 0.5);
+        assert!(matches!(enclose_origin(&a, &apart, 1), Outcome::Undecided));
+        assert!(!intersects_within(&a, &apart, 1));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::geometry::PointF;
+
+struct Feature {
+    farthest: PointF,
+    start: PointF,
+    end: PointF,
+}
+
+impl Feature {
+    fn direction(&self) -> PointF {
+        let edge = self.end - self.start;
+        edge / edge.length()
+    }
+}
+
+// Edge of the convex polygon facing the direction the most.
+fn best_edge(vertices: &[PointF], direction: PointF) -> Feature {
+    let count = vertices.len();
+    let index = (0..count)
+        .max_by(|&i, &j| {
+            vertices[i]
+                .dot(direction)
+                .total_cmp(&vertices[j].dot(direction))
+        })
+        .unwrap();
+    let farthest = vertices[index];
+    let previous = vertices[(index + count - 1) % count];
+    let next = vertices[(index + 1) % count];
+
+    let along = |other: PointF| {
+        let edge = farthest - other;
+        let length = edge.length();
+        if length == 0.0 {
+            f64::INFINITY
+        } else {
+            (edge / length).dot(direction).abs()
+        }
+    };
+    if along(previous) <= along(next) {
+        Feature {
+            farthest,
+            start: previous,
+            end: farthest,
+        }
+    } else {
+        Feature {
+            farthest,
+            start: farthest,
+            end: next,
+        }
+    }
+}
+
+// Keeps the part of the segment where `direction · point >= offset`.
+fn clip(points: Vec<PointF>, direction: PointF, offset: f64) -> Vec<PointF> {
+    let [p, q] = points[..] else {
+        return points;
+    };
+    let (dp, dq) = (direction.dot(p) - offset, direction.dot(q) - offset);
+
+    let mut clipped = Vec::with_capacity(2);
+    if dp >= 0.0 {
+        clipped.push(p);
+    }
+    if dq >= 0.0 {
+        clipped.push(q);
+    }
+    if dp * dq < 0.0 {
+        clipped.push(p + (q - p) * (dp / (dp - dq)));
+    }
+    clipped
+}
+
+/// Contact points between two overlapping convex polygons, found by clipping the incident edge
+/// against the reference edge (the one most perpendicular to the normal). `normal` is the unit
+/// collision normal pointing from `a` toward `b`. The points lie on the incident polygon.
+pub(crate) fn contact_points(a: &[PointF], b: &[PointF], normal: PointF) -> Vec<PointF> {
+    if a.len() < 2 || b.len() < 2 {
+        return Vec::new();
+    }
+
+    let edge_a = best_edge(a, normal);
+    let edge_b = best_edge(b, -normal);
+    let (reference, incident, face_normal) =
+        if edge_a.direction().dot(normal).abs() <= edge_b.direction().dot(normal).abs() {
+            (edge_a, edge_b, normal)
+        } else {
+            (edge_b, edge_a, -normal)
+        };
+
+    let direction = reference.direction();
+    let points = clip(
+        vec![incident.start, incident.end],
+        direction,
+        direction.dot(reference.start),
+    );
+    let points = clip(points, -direction, -direction.dot(reference.end));
+
+    let mut perpendicular = PointF::new(-direction.y, direction.x);
+    if perpendicular.dot(face_normal) < 0.0 {
+        perpendicular = -perpendicular;
+    }
+    let face = perpendicular.dot(reference.farthest);
+    points
+        .into_iter()
+        .filter(|&point| perpendicular.dot(point) <= face)
+        .collect()
+}
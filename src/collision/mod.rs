@@ -0,0 +1,339 @@
+mod epa;
+mod gjk;
+mod manifold;
+pub mod sat;
+
+use crate::geometry::{BoundingBox, PointF, Polygon, Triangle, TriangleF, Vertex};
+use serde::{Deserialize, Serialize};
+
+/// A convex piece of a shape, such as the triangles returned by
+/// [`triangulate`](crate::triangulation::triangulate).
+pub trait ConvexShape {
+    fn vertices(&self) -> Vec<PointF>;
+}
+
+impl ConvexShape for Triangle {
+    fn vertices(&self) -> Vec<PointF> {
+        vec![self.0.into(), self.1.into(), self.2.into()]
+    }
+}
+
+impl ConvexShape for TriangleF {
+    fn vertices(&self) -> Vec<PointF> {
+        vec![self.0, self.1, self.2]
+    }
+}
+
+/// The polygon is expected to be convex.
+impl<P: Vertex> ConvexShape for Polygon<P> {
+    fn vertices(&self) -> Vec<PointF> {
+        self.iter().map(|&point| point.into()).collect()
+    }
+}
+
+/// The Minkowski difference `a - b` of two convex pieces, as a clockwise convex polygon. The
+/// pieces overlap exactly when it contains the origin.
+pub fn minkowski_difference<A, B>(a: &A, b: &B) -> Polygon<PointF>
+where
+    A: ConvexShape + ?Sized,
+    B: ConvexShape + ?Sized,
+{
+    let b = b.vertices();
+    let differences = a
+        .vertices()
+        .into_iter()
+        .flat_map(|p| b.iter().map(move |&q| p - q))
+        .collect();
+    convex_hull(differences)
+}
+
+/// Andrew's monotone chain, returning a clockwise hull without collinear points.
+pub(crate) fn convex_hull(mut points: Vec<PointF>) -> Polygon<PointF> {
+    points.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return Polygon::from(points);
+    }
+
+    let mut hull: Vec<PointF> = Vec::with_capacity(points.len() + 1);
+    for pass in 0..2 {
+        let start = hull.len();
+        for &point in points.iter() {
+            while hull.len() >= start + 2
+                && (hull[hull.len() - 1] - hull[hull.len() - 2]).cross(point - hull[hull.len() - 2])
+                    <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+        if pass == 0 {
+            points.reverse();
+        }
+    }
+    Polygon::from(hull)
+}
+
+/// A convex piece moved to its place in the world, with its bounding box.
+pub(crate) struct Piece {
+    pub(crate) vertices: Vec<PointF>,
+    pub(crate) bounding_box: BoundingBox,
+}
+
+pub(crate) fn pieces<T: ConvexShape>(shape: &[T], offset: PointF) -> Vec<Piece> {
+    shape
+        .iter()
+        .filter_map(|piece| {
+            let vertices: Vec<PointF> = piece
+                .vertices()
+                .into_iter()
+                .map(|vertex| vertex + offset)
+                .collect();
+            let bounding_box = BoundingBox::from_points(vertices.iter().copied())?;
+            Some(Piece {
+                vertices,
+                bounding_box,
+            })
+        })
+        .collect()
+}
+
+fn boxes_distance(a: &BoundingBox, b: &BoundingBox) -> f64 {
+    let dx = (a.min.x - b.max.x).max(b.min.x - a.max.x).max(0.0);
+    let dy = (a.min.y - b.max.y).max(b.min.y - a.max.y).max(0.0);
+    PointF::new(dx, dy).length()
+}
+
+/// Whether two convex pieces placed at the given offsets overlap or touch, using GJK.
+pub fn convex_intersects<A, B>(a: &A, a_offset: PointF, b: &B, b_offset: PointF) -> bool
+where
+    A: ConvexShape + ?Sized,
+    B: ConvexShape + ?Sized,
+{
+    let a: Vec<PointF> = a.vertices().into_iter().map(|p| p + a_offset).collect();
+    let b: Vec<PointF> = b.vertices().into_iter().map(|p| p + b_offset).collect();
+    !a.is_empty() && !b.is_empty() && gjk::intersects(&a, &b)
+}
+
+/// Distance between two convex pieces placed at the given offsets, `0.0` when they overlap.
+pub fn convex_distance<A, B>(a: &A, a_offset: PointF, b: &B, b_offset: PointF) -> f64
+where
+    A: ConvexShape + ?Sized,
+    B: ConvexShape + ?Sized,
+{
+    let a: Vec<PointF> = a.vertices().into_iter().map(|p| p + a_offset).collect();
+    let b: Vec<PointF> = b.vertices().into_iter().map(|p| p + b_offset).collect();
+    if a.is_empty() || b.is_empty() {
+        return f64::INFINITY;
+    }
+    gjk::distance(&a, &b)
+}
+
+/// How two overlapping convex pieces touch. Moving `b` by `normal * depth`, or `a` by the opposite,
+/// separates them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    /// Unit vector pointing from `a` toward `b`.
+    pub normal: PointF,
+    pub depth: f64,
+    /// One or two points of the contact manifold, in world coordinates.
+    pub points: Vec<PointF>,
+}
+
+fn contact(a: &[PointF], b: &[PointF]) -> Option<Contact> {
+    let simplex = gjk::intersection(a, b)?;
+    let (normal, depth) = epa::penetration(a, b, simplex)?;
+    let mut points = manifold::contact_points(a, b, normal);
+    if points.is_empty() {
+        points.push(gjk::farthest(b, -normal));
+    }
+    Some(Contact {
+        normal,
+        depth,
+        points,
+    })
+}
+
+/// Penetration and contact points of two convex pieces placed at the given offsets, using GJK
+/// and the Expanding Polytope Algorithm. Returns `None` when they do not overlap or only touch.
+pub fn convex_contact<A, B>(a: &A, a_offset: PointF, b: &B, b_offset: PointF) -> Option<Contact>
+where
+    A: ConvexShape + ?Sized,
+    B: ConvexShape + ?Sized,
+{
+    let a: Vec<PointF> = a.vertices().into_iter().map(|p| p + a_offset).collect();
+    let b: Vec<PointF> = b.vertices().into_iter().map(|p| p + b_offset).collect();
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    contact(&a, &b)
+}
+
+/// Contacts between every overlapping pair of pieces of two shapes placed at the given offsets.
+pub fn contacts<A, B>(a: &[A], a_offset: PointF, b: &[B], b_offset: PointF) -> Vec<Contact>
+where
+    A: ConvexShape,
+    B: ConvexShape,
+{
+    let b = pieces(b, b_offset);
+    pieces(a, a_offset)
+        .iter()
+        .flat_map(|a| {
+            b.iter()
+                .filter(|b| a.bounding_box.intersects(&b.bounding_box))
+                .filter_map(|b| contact(&a.vertices, &b.vertices))
+        })
+        .collect()
+}
+
+/// Whether two shapes made of convex pieces, e.g. triangulations, overlap when placed at the given
+/// offsets.
+pub fn intersects<A, B>(a: &[A], a_offset: PointF, b: &[B], b_offset: PointF) -> bool
+where
+    A: ConvexShape,
+    B: ConvexShape,
+{
+    let b = pieces(b, b_offset);
+    pieces(a, a_offset).iter().any(|a| {
+        b.iter().any(|b| {
+            a.bounding_box.intersects(&b.bounding_box) && gjk::intersects(&a.vertices, &b.vertices)
+        })
+    })
+}
+
+/// Smallest distance between two shapes made of convex pieces placed at the given offsets, `0.0`
+/// when they overlap and infinity when one of them is empty.
+pub fn distance<A, B>(a: &[A], a_offset: PointF, b: &[B], b_offset: PointF) -> f64
+where
+    A: ConvexShape,
+    B: ConvexShape,
+{
+    let b = pieces(b, b_offset);
+    let mut best = f64::INFINITY;
+    for a in pieces(a, a_offset) {
+        for b in b.iter() {
+            if boxes_distance(&a.bounding_box, &b.bounding_box) >= best {
+                continue;
+            }
+            best = best.min(gjk::distance(&a.vertices, &b.vertices));
+            if best == 0.0 {
+                return best;
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Point, Winding};
+
+    fn square(size: f64) -> Polygon<PointF> {
+        Polygon::from(vec![
+            PointF::new(0.0, 0.0),
+            PointF::new(size, 0.0),
+            PointF::new(size, size),
+            PointF::new(0.0, size),
+        ])
+    }
+
+    fn triangles() -> Vec<Triangle> {
+        vec![
+            Triangle(Point::new(0, 0), Point::new(4, 0), Point::new(4, 4)),
+            Triangle(Point::new(0, 0), Point::new(4, 4), Point::new(0, 4)),
+        ]
+    }
+
+    #[test]
+    fn test_minkowski_difference() {
+        let difference = minkowski_difference(&square(2.0), &square(1.0));
+        assert_eq!(difference.len(), 4);
+        assert_eq!(difference.area(), 9.0);
+        assert_eq!(difference.winding(), Some(Winding::Clockwise));
+        assert!(difference.contains_point(PointF::new(0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_convex_queries() {
+        let a = square(2.0);
+        let b = square(1.0);
+        assert!(convex_intersects(
+            &a,
+            PointF::default(),
+            &b,
+            PointF::new(1.5, 1.5)
+        ));
+        assert!(convex_intersects(
+            &a,
+            PointF::default(),
+            &b,
+            PointF::new(2.0, 0.5)
+        ));
+        assert!(!convex_intersects(
+            &a,
+            PointF::default(),
+            &b,
+            PointF::new(3.0, 0.5)
+        ));
+
+        assert_eq!(
+            convex_distance(&a, PointF::default(), &b, PointF::new(1.5, 1.5)),
+            0.0
+        );
+        let distance = convex_distance(&a, PointF::default(), &b, PointF::new(5.0, 6.0));
+        assert!((distance - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convex_contact() {
+        let a = square(2.0);
+        let b = square(2.0);
+        let contact = convex_contact(&a, PointF::default(), &b, PointF::new(1.5, 0.5)).unwrap();
+        assert!((contact.normal.x - 1.0).abs() < 1e-9);
+        assert!(contact.normal.y.abs() < 1e-9);
+        assert!((contact.depth - 0.5).abs() < 1e-9);
+        assert_eq!(contact.points.len(), 2);
+        for point in contact.points {
+            assert!((1.5..=2.0).contains(&point.x));
+            assert!((0.5..=2.0).contains(&point.y));
+        }
+
+        assert!(convex_contact(&a, PointF::default(), &b, PointF::new(2.0, 0.0)).is_none());
+        assert!(convex_contact(&a, PointF::default(), &b, PointF::new(3.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn test_contacts() {
+        let a = triangles();
+        let b = vec![Triangle(
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(2, 3),
+        )];
+        let contacts = contacts(&a, PointF::default(), &b, PointF::new(0.0, 3.0));
+        assert!(!contacts.is_empty());
+        assert!(contacts.iter().all(|contact| contact.depth > 0.0));
+    }
+
+    #[test]
+    fn test_shape_queries() {
+        let a = triangles();
+        let b = triangles();
+        assert!(intersects(&a, PointF::default(), &b, PointF::new(3.0, 3.0)));
+        assert!(!intersects(
+            &a,
+            PointF::default(),
+            &b,
+            PointF::new(6.0, 0.0)
+        ));
+
+        assert_eq!(
+            distance(&a, PointF::default(), &b, PointF::new(3.0, 3.0)),
+            0.0
+        );
+        let distance = distance(&a, PointF::default(), &b, PointF::new(7.0, 8.0));
+        assert!((distance - 5.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,266 @@
+//! Separating Axis Theorem queries, a deterministic alternative to the GJK based ones.
+
+use super::{pieces, ConvexShape};
+use crate::geometry::{BoundingBox, PointF};
+
+// Unit normals of the edges of a convex polygon. The edges of a flat piece, a segment or a point,
+// give their direction as well: the normal alone can't separate two pieces on the same line.
+fn axes(vertices: &[PointF]) -> Vec<PointF> {
+    let directions: Vec<PointF> = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .filter_map(|(&p, &q)| {
+            let edge = q - p;
+            let length = edge.length();
+            (length > 0.0).then(|| edge / length)
+        })
+        .collect();
+    let flat = directions.first().is_none_or(|&direction| {
+        vertices
+            .iter()
+            .all(|&vertex| direction.cross(vertex - vertices[0]) == 0.0)
+    });
+
+    let normals = directions
+        .iter()
+        .map(|direction| PointF::new(-direction.y, direction.x));
+    if flat {
+        normals.chain(directions.iter().copied()).collect()
+    } else {
+        normals.collect()
+    }
+}
+
+fn project(vertices: &[PointF], axis: PointF) -> (f64, f64) {
+    vertices
+        .iter()
+        .map(|vertex| vertex.dot(axis))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+// Smallest overlap over every axis, as the vector moving `b` out of `a`. The overlap is negative
+// as soon as one axis separates the polygons. Two pieces reduced to a point have no axis, and
+// only overlap when they are the same point.
+fn smallest_overlap(a: &[PointF], b: &[PointF]) -> (PointF, f64) {
+    let mut best: Option<(PointF, f64)> = None;
+    for axis in axes(a).into_iter().chain(axes(b)) {
+        let (min_a, max_a) = project(a, axis);
+        let (min_b, max_b) = project(b, axis);
+        let (forward, backward) = (max_a - min_b, max_b - min_a);
+        let (direction, overlap) = if forward <= backward {
+            (axis, forward)
+        } else {
+            (-axis, backward)
+        };
+
+        if overlap < 0.0 {
+            return (direction, overlap);
+        }
+        if best.is_none_or(|(_, depth)| overlap < depth) {
+            best = Some((direction, overlap));
+        }
+    }
+    best.unwrap_or_else(|| {
+        let offset = b[0] - a[0];
+        let distance = offset.length();
+        let direction = if distance > 0.0 {
+            offset / distance
+        } else {
+            PointF::default()
+        };
+        (direction, -distance)
+    })
+}
+
+fn translated<T: ConvexShape + ?Sized>(shape: &T, offset: PointF) -> Vec<PointF> {
+    shape
+        .vertices()
+        .into_iter()
+        .map(|vertex| vertex + offset)
+        .collect()
+}
+
+/// Whether two convex pieces placed at the given offsets overlap or touch.
+pub fn overlaps<A, B>(a: &A, a_offset: PointF, b: &B, b_offset: PointF) -> bool
+where
+    A: ConvexShape + ?Sized,
+    B: ConvexShape + ?Sized,
+{
+    let (a, b) = (translated(a, a_offset), translated(b, b_offset));
+    !a.is_empty() && !b.is_empty() && smallest_overlap(&a, &b).1 >= 0.0
+}
+
+/// Shortest vector moving `b` out of `a` when the two convex pieces placed at the given offsets
+/// overlap. Returns `None` when they are apart or only touch.
+pub fn minimum_translation<A, B>(a: &A, a_offset: PointF, b: &B, b_offset: PointF) -> Option<PointF>
+where
+    A: ConvexShape + ?Sized,
+    B: ConvexShape + ?Sized,
+{
+    let (a, b) = (translated(a, a_offset), translated(b, b_offset));
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let (direction, overlap) = smallest_overlap(&a, &b);
+    (overlap > 0.0).then(|| direction * overlap)
+}
+
+/// Whether two shapes made of convex pieces, e.g. triangulations, overlap when placed at the given
+/// offsets. The bounding boxes of the whole shapes and of every pair of pieces are compared before
+/// running the axis tests.
+pub fn shapes_overlap<A, B>(a: &[A], a_offset: PointF, b: &[B], b_offset: PointF) -> bool
+where
+    A: ConvexShape,
+    B: ConvexShape,
+{
+    let (a, b) = (pieces(a, a_offset), pieces(b, b_offset));
+    let bounds = |pieces: &[super::Piece]| {
+        BoundingBox::from_points(
+            pieces
+                .iter()
+                .flat_map(|piece| [piece.bounding_box.min, piece.bounding_box.max]),
+        )
+    };
+    match (bounds(&a), bounds(&b)) {
+        (Some(bounds_a), Some(bounds_b)) if bounds_a.intersects(&bounds_b) => {}
+        _ => return false,
+    }
+
+    a.iter().any(|a| {
+        b.iter().any(|b| {
+            a.bounding_box.intersects(&b.bounding_box)
+                && smallest_overlap(&a.vertices, &b.vertices).1 >= 0.0
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Point, Polygon, Triangle};
+
+    #[test]
+    fn test_triangles() {
+        let a = Triangle(Point::new(0, 0), Point::new(4, 0), Point::new(0, 4));
+        let b = Triangle(Point::new(0, 0), Point::new(2, 0), Point::new(0, 2));
+
+        assert!(overlaps(&a, PointF::default(), &b, PointF::new(1.0, 1.0)));
+        assert!(overlaps(&a, PointF::default(), &b, PointF::new(2.0, 2.0)));
+        assert!(!overlaps(&a, PointF::default(), &b, PointF::new(3.0, 3.0)));
+        assert_eq!(
+            minimum_translation(&a, PointF::default(), &b, PointF::new(2.0, 2.0)),
+            None
+        );
+    }
+
+    fn piece(coordinates: &[(f64, f64)]) -> Polygon<PointF> {
+        coordinates
+            .iter()
+            .map(|&(x, y)| PointF::new(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn test_points() {
+        let point = piece(&[(0.0, 0.0)]);
+        let origin = PointF::default();
+
+        assert!(!overlaps(&point, origin, &point, PointF::new(100.0, 100.0)));
+        assert!(overlaps(&point, origin, &point, origin));
+        assert_eq!(minimum_translation(&point, origin, &point, origin), None);
+    }
+
+    #[test]
+    fn test_segments() {
+        let segment = piece(&[(0.0, 0.0), (10.0, 0.0)]);
+        let origin = PointF::default();
+
+        // On the same line
+        assert!(!overlaps(
+            &segment,
+            origin,
+            &segment,
+            PointF::new(20.0, 0.0)
+        ));
+        assert!(overlaps(&segment, origin, &segment, PointF::new(5.0, 0.0)));
+        // Parallel
+        assert!(!overlaps(&segment, origin, &segment, PointF::new(5.0, 1.0)));
+        // Crossing
+        let vertical = piece(&[(5.0, -5.0), (5.0, 5.0)]);
+        assert!(overlaps(&segment, origin, &vertical, origin));
+        // A point on the segment, and one on its line past its end
+        let point = piece(&[(0.0, 0.0)]);
+        assert!(overlaps(&segment, origin, &point, PointF::new(4.0, 0.0)));
+        assert!(!overlaps(&segment, origin, &point, PointF::new(12.0, 0.0)));
+    }
+
+    #[test]
+    fn test_segment_and_triangle() {
+        let triangle = Triangle(Point::new(0, 0), Point::new(4, 0), Point::new(0, 4));
+        let segment = piece(&[(0.0, 0.0), (4.0, 4.0)]);
+        let origin = PointF::default();
+
+        assert!(overlaps(&triangle, origin, &segment, origin));
+        assert!(overlaps(
+            &triangle,
+            origin,
+            &segment,
+            PointF::new(1.0, -2.0)
+        ));
+        assert!(!overlaps(
+            &triangle,
+            origin,
+            &segment,
+            PointF::new(5.0, 0.0)
+        ));
+        assert!(!overlaps(
+            &triangle,
+            origin,
+            &segment,
+            PointF::new(-5.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn test_minimum_translation() {
+        let square = Polygon::from(vec![
+            PointF::new(0.0, 0.0),
+            PointF::new(2.0, 0.0),
+            PointF::new(2.0, 2.0),
+            PointF::new(0.0, 2.0),
+        ]);
+        let translation =
+            minimum_translation(&square, PointF::default(), &square, PointF::new(-1.5, 0.5))
+                .unwrap();
+        assert!((translation.x + 0.5).abs() < 1e-9);
+        assert!(translation.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_shapes_overlap() {
+        let a = vec![
+            Triangle(Point::new(0, 0), Point::new(4, 0), Point::new(4, 4)),
+            Triangle(Point::new(0, 0), Point::new(4, 4), Point::new(0, 4)),
+        ];
+        assert!(shapes_overlap(
+            &a,
+            PointF::default(),
+            &a,
+            PointF::new(3.0, -3.0)
+        ));
+        assert!(!shapes_overlap(
+            &a,
+            PointF::default(),
+            &a,
+            PointF::new(4.5, 0.0)
+        ));
+        assert!(!shapes_overlap::<Triangle, Triangle>(
+            &a,
+            PointF::default(),
+            &[],
+            PointF::default()
+        ));
+    }
+}
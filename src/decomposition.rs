@@ -0,0 +1,450 @@
+//! Convex decomposition of polygons, an alternative to triangulation for physics engines that
+//! prefer a few convex pieces over many thin triangles.
+//!
+//! Every piece is wound clockwise, like the outer rings produced by [`crate::image`].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::*;
+use crate::triangulation::{triangulate, TriangulationError, TriangulationOptions};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecompositionOptions {
+    /// Maximum number of vertices of a piece. Values below 3 are treated as 3.
+    pub max_vertices: usize,
+}
+
+impl Default for DecompositionOptions {
+    fn default() -> Self {
+        Self { max_vertices: 8 }
+    }
+}
+
+impl DecompositionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_vertices(mut self, max_vertices: usize) -> Self {
+        self.max_vertices = max_vertices;
+        self
+    }
+
+    fn cap(&self) -> usize {
+        self.max_vertices.max(3)
+    }
+}
+
+/// Hertel–Mehlhorn decomposition: triangulates the polygon, then merges the triangles into convex
+/// pieces by removing the diagonals that are not needed.
+pub fn hertel_mehlhorn<P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: Option<&Vec<Polygon<P>>>,
+    options: &DecompositionOptions,
+) -> Result<Vec<Polygon<P>>, TriangulationError> {
+    let triangles = triangulate(polygon, holes, &TriangulationOptions::default())?;
+    Ok(merge_triangles(&triangles, options))
+}
+
+/// Greedily merges adjacent triangles into convex pieces. Triangles are adjacent when they share
+/// an edge with the exact same end points, as the output of [`triangulate`] does.
+pub fn merge_triangles<T: Face>(
+    triangles: &[T],
+    options: &DecompositionOptions,
+) -> Vec<Polygon<T::Vertex>> {
+    let max_vertices = options.cap();
+
+    let mut vertices: Vec<T::Vertex> = vec![];
+    let mut ids: HashMap<(u64, u64), usize> = HashMap::new();
+    let mut pieces: Vec<Option<Vec<usize>>> = vec![];
+    for triangle in triangles {
+        let mut corners = triangle.vertices();
+        let [a, b, c] = corners.map(Into::<PointF>::into);
+        let area = (b - a).cross(c - a);
+        if area == 0.0 {
+            continue;
+        }
+        if area < 0.0 {
+            corners.swap(1, 2);
+        }
+        let piece = corners.map(|vertex| {
+            let point: PointF = vertex.into();
+            *ids.entry((point.x.to_bits(), point.y.to_bits()))
+                .or_insert_with(|| {
+                    vertices.push(vertex);
+                    vertices.len() - 1
+                })
+        });
+        pieces.push(Some(piece.to_vec()));
+    }
+
+    // Owner of every directed edge, the diagonals being the edges whose reverse is owned too.
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    for (index, piece) in pieces.iter().enumerate() {
+        for edge in ring_edges(piece.as_deref().unwrap_or_default()) {
+            edges.insert(edge, index);
+        }
+    }
+
+    let point = |id: usize| -> PointF { vertices[id].into() };
+    let mut index = 0;
+    while index < pieces.len() {
+        let merged = pieces[index].as_deref().and_then(|piece| {
+            ring_edges(piece).find_map(|(a, b)| {
+                let other = *edges.get(&(b, a))?;
+                if other == index {
+                    return None;
+                }
+                let merged = join(piece, pieces[other].as_deref()?, a, b);
+                let ring: Vec<PointF> = merged.iter().map(|&id| point(id)).collect();
+                (merged.len() <= max_vertices && is_convex(&ring)).then_some((other, merged))
+            })
+        });
+
+        match merged {
+            Some((other, merged)) => {
+                for piece in [pieces[index].take(), pieces[other].take()] {
+                    for edge in ring_edges(piece.as_deref().unwrap_or_default()) {
+                        edges.remove(&edge);
+                    }
+                }
+                for edge in ring_edges(&merged) {
+                    edges.insert(edge, index);
+                }
+                pieces[index] = Some(merged);
+            }
+            None => index += 1,
+        }
+    }
+
+    pieces
+        .into_iter()
+        .flatten()
+        .map(|piece| piece.into_iter().map(|id| vertices[id]).collect())
+        .collect()
+}
+
+/// Bayazit decomposition of a simple polygon without holes. It cuts the polygon at its reflex
+/// vertices, adding Steiner points where no vertex can be reached, and usually gives fewer pieces
+/// than [`hertel_mehlhorn`]. Use the latter for polygons with holes.
+///
+/// Parts of nearly degenerate rings that can't be cut are triangulated instead, whose error is
+/// returned.
+pub fn bayazit<P: Vertex>(
+    polygon: &Polygon<P>,
+    options: &DecompositionOptions,
+) -> Result<Vec<Polygon<PointF>>, TriangulationError> {
+    let mut ring: Vec<PointF> = polygon.iter().map(|&point| point.into()).collect();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    let mut ring = Polygon::from(cleaned(ring));
+    if ring.len() < 3 {
+        return Ok(vec![]);
+    }
+    if ring.signed_area() < 0.0 {
+        ring.reverse();
+    }
+
+    let mut convex = vec![];
+    let mut pending = vec![ring.into_vec()];
+    while let Some(ring) = pending.pop() {
+        match split_at_reflex(&ring) {
+            None => convex.push(ring),
+            Some(Some((lower, upper))) if lower.len() < ring.len() && upper.len() < ring.len() => {
+                pending.extend(
+                    [cleaned(lower), cleaned(upper)]
+                        .into_iter()
+                        .filter(|ring| ring.len() >= 3),
+                );
+            }
+            // No usable cut was found, which only happens on nearly degenerate rings
+            Some(_) => {
+                let triangles =
+                    triangulate(&Polygon::from(ring), None, &TriangulationOptions::default())?;
+                convex.extend(
+                    merge_triangles(&triangles, options)
+                        .into_iter()
+                        .map(Polygon::into_vec),
+                );
+            }
+        }
+    }
+
+    let mut pieces = vec![];
+    for ring in convex {
+        split_convex(ring, options.cap(), &mut pieces);
+    }
+    Ok(pieces)
+}
+
+fn ring_edges(ring: &[usize]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+// Joins two pieces along the edge `a -> b` of `piece`, which is `b -> a` in `other`.
+fn join(piece: &[usize], other: &[usize], a: usize, b: usize) -> Vec<usize> {
+    let start = piece.iter().position(|&id| id == b).unwrap_or_default();
+    let mut ring: Vec<usize> = piece[start..]
+        .iter()
+        .chain(&piece[..start])
+        .copied()
+        .collect();
+    let start = other.iter().position(|&id| id == a).unwrap_or_default();
+    ring.extend(
+        other[start..]
+            .iter()
+            .chain(&other[..start])
+            .skip(1)
+            .take(other.len() - 2),
+    );
+    ring
+}
+
+fn turn(a: PointF, b: PointF, c: PointF) -> f64 {
+    (b - a).cross(c - a)
+}
+
+fn is_convex(ring: &[PointF]) -> bool {
+    let n = ring.len();
+    (0..n).all(|i| turn(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]) >= 0.0)
+}
+
+// Removes repeated and collinear vertices, which would otherwise be mistaken for reflex ones.
+fn cleaned(mut ring: Vec<PointF>) -> Vec<PointF> {
+    loop {
+        let n = ring.len();
+        if n < 3 {
+            return ring;
+        }
+        let redundant = (0..n).find(|&i| {
+            let (previous, next) = (ring[(i + n - 1) % n], ring[(i + 1) % n]);
+            let (incoming, outgoing) = (ring[i] - previous, next - ring[i]);
+            incoming.cross(outgoing).abs() <= 1e-9 * incoming.length() * outgoing.length()
+        });
+        match redundant {
+            Some(i) => {
+                ring.remove(i);
+            }
+            None => return ring,
+        }
+    }
+}
+
+fn line_intersection(a1: PointF, a2: PointF, b1: PointF, b2: PointF) -> PointF {
+    let (r, s) = (a2 - a1, b2 - b1);
+    a1 + r * ((b1 - a1).cross(s) / r.cross(s))
+}
+
+fn cyclic(ring: &[PointF], from: usize, to: usize) -> Vec<PointF> {
+    let n = ring.len();
+    let mut slice = vec![ring[from]];
+    let mut k = from;
+    while k != to {
+        k = (k + 1) % n;
+        slice.push(ring[k]);
+    }
+    slice
+}
+
+// Cuts the ring at its first reflex vertex. Returns `None` when the ring is convex, and
+// `Some(None)` when no cut could be found.
+#[allow(clippy::type_complexity)]
+fn split_at_reflex(ring: &[PointF]) -> Option<Option<(Vec<PointF>, Vec<PointF>)>> {
+    let n = ring.len();
+    let at = |i: usize| ring[i % n];
+    let i = (0..n).find(|&i| turn(at(i + n - 1), at(i), at(i + 1)) < 0.0)?;
+    let (previous, current, next) = (at(i + n - 1), at(i), at(i + 1));
+
+    // Closest hits of the two edges around the reflex vertex, extended through it
+    let mut lower: Option<(f64, PointF, usize)> = None;
+    let mut upper: Option<(f64, PointF, usize)> = None;
+    for j in 0..n {
+        let (before, vertex, after) = (at(j + n - 1), at(j), at(j + 1));
+        if turn(previous, current, vertex) > 0.0 && turn(previous, current, before) <= 0.0 {
+            let hit = line_intersection(previous, current, vertex, before);
+            let distance = hit.distance(current);
+            if turn(next, current, hit) < 0.0 && lower.is_none_or(|(best, ..)| distance < best) {
+                lower = Some((distance, hit, j));
+            }
+        }
+        if turn(next, current, after) > 0.0 && turn(next, current, vertex) <= 0.0 {
+            let hit = line_intersection(next, current, vertex, after);
+            let distance = hit.distance(current);
+            if turn(previous, current, hit) > 0.0 && upper.is_none_or(|(best, ..)| distance < best)
+            {
+                upper = Some((distance, hit, j));
+            }
+        }
+    }
+    let (Some((_, lower_hit, lower_index)), Some((_, upper_hit, upper_index))) = (lower, upper)
+    else {
+        return Some(None);
+    };
+
+    if lower_index == (upper_index + 1) % n {
+        // Both hits are on the same edge with no vertex in between, cut to its middle
+        let steiner = (lower_hit + upper_hit) / 2.0;
+        let mut lower = cyclic(ring, i, upper_index);
+        lower.push(steiner);
+        let mut upper = vec![steiner];
+        upper.extend(cyclic(ring, lower_index, i));
+        return Some(Some((lower, upper)));
+    }
+
+    // Otherwise cut to the closest vertex between the hits that can see the reflex vertex
+    let last = if lower_index > upper_index {
+        upper_index + n
+    } else {
+        upper_index
+    };
+    let closest = (lower_index..=last)
+        .map(|j| j % n)
+        .filter(|&j| j != i && j != (i + 1) % n && j != (i + n - 1) % n)
+        .filter(|&j| turn(previous, current, ring[j]) >= 0.0 && turn(next, current, ring[j]) <= 0.0)
+        .filter(|&j| can_see(ring, i, j))
+        .min_by(|&a, &b| {
+            ring[a]
+                .distance(current)
+                .total_cmp(&ring[b].distance(current))
+        });
+
+    Some(closest.map(|j| (cyclic(ring, i, j), cyclic(ring, j, i))))
+}
+
+// Whether the diagonal between two vertices doesn't cross or touch the rest of the ring.
+fn can_see(ring: &[PointF], i: usize, j: usize) -> bool {
+    let n = ring.len();
+    let (a, b) = (ring[i], ring[j]);
+    let on_segment = |p: PointF| turn(a, b, p) == 0.0 && (p - a).dot(p - b) < 0.0;
+    (0..n).all(|k| {
+        let l = (k + 1) % n;
+        if [k, l].iter().any(|&end| end == i || end == j) {
+            return true;
+        }
+        let (c, d) = (ring[k], ring[l]);
+        let crosses = turn(a, b, c) * turn(a, b, d) < 0.0 && turn(c, d, a) * turn(c, d, b) < 0.0;
+        !crosses && !on_segment(c)
+    })
+}
+
+// Splits a convex ring into fans of at most `max_vertices` vertices.
+fn split_convex<P: Copy>(mut ring: Vec<P>, max_vertices: usize, pieces: &mut Vec<Polygon<P>>) {
+    while ring.len() > max_vertices {
+        pieces.push(Polygon::from(&ring[..max_vertices]));
+        ring.drain(1..max_vertices - 1);
+    }
+    pieces.push(Polygon::from(ring));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_convex_piece<P: Vertex>(piece: &Polygon<P>) -> bool {
+        let ring: Vec<PointF> = piece.iter().map(|&point| point.into()).collect();
+        piece.len() >= 3 && piece.signed_area() > 0.0 && is_convex(&ring)
+    }
+
+    fn total_area<P: Vertex>(pieces: &[Polygon<P>]) -> f64 {
+        pieces.iter().map(Polygon::area).sum()
+    }
+
+    #[test]
+    fn test_square_is_one_piece() {
+        let square = points(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        let pieces = hertel_mehlhorn(&square, None, &DecompositionOptions::new()).unwrap();
+
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 4);
+        assert_eq!(pieces[0].winding(), Some(Winding::Clockwise));
+    }
+
+    #[test]
+    fn test_hertel_mehlhorn_l_shape() {
+        let l_shape = points(&[(0, 0), (2, 0), (2, 4), (6, 4), (6, 6), (0, 6)]);
+        let pieces = hertel_mehlhorn(&l_shape, None, &DecompositionOptions::new()).unwrap();
+
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(is_convex_piece));
+        assert_eq!(total_area(&pieces), l_shape.area());
+    }
+
+    #[test]
+    fn test_hertel_mehlhorn_with_hole() {
+        let outer = points(&[(0, 0), (10, 0), (10, 10), (0, 10)]);
+        let hole = points(&[(3, 3), (3, 7), (7, 7), (7, 3)]);
+        let pieces =
+            hertel_mehlhorn(&outer, Some(&vec![hole]), &DecompositionOptions::new()).unwrap();
+
+        assert!(pieces.iter().all(is_convex_piece));
+        assert_eq!(total_area(&pieces), 84.0);
+    }
+
+    #[test]
+    fn test_max_vertices() {
+        let circle: Polygon<PointF> = (0..24)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / 24.0;
+                PointF::new(angle.cos() * 10.0, angle.sin() * 10.0)
+            })
+            .collect();
+        let options = DecompositionOptions::new().max_vertices(5);
+
+        for pieces in [
+            hertel_mehlhorn(&circle, None, &options).unwrap(),
+            bayazit(&circle, &options).unwrap(),
+        ] {
+            assert!(pieces.iter().all(|piece| piece.len() <= 5));
+            assert!(pieces.iter().all(is_convex_piece));
+            assert!((total_area(&pieces) - circle.area()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bayazit_l_shape() {
+        let l_shape = points(&[(0, 0), (2, 0), (2, 4), (6, 4), (6, 6), (0, 6)]);
+        let pieces = bayazit(&l_shape, &DecompositionOptions::new()).unwrap();
+
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(is_convex_piece));
+        assert_eq!(total_area(&pieces), l_shape.area());
+    }
+
+    #[test]
+    fn test_bayazit_comb() {
+        // Counter-clockwise on purpose, with a Steiner point needed between the teeth
+        let comb = points(&[
+            (0, 0),
+            (0, 10),
+            (12, 10),
+            (12, 0),
+            (10, 0),
+            (10, 6),
+            (8, 6),
+            (8, 0),
+            (6, 0),
+            (6, 6),
+            (4, 6),
+            (4, 0),
+            (2, 0),
+            (2, 6),
+        ]);
+        assert_eq!(comb.winding(), Some(Winding::CounterClockwise));
+        let pieces = bayazit(&comb, &DecompositionOptions::new()).unwrap();
+
+        assert!(pieces.iter().all(is_convex_piece));
+        assert!((total_area(&pieces) - comb.area()).abs() < 1e-9);
+        assert!(
+            pieces.len()
+                < triangulate(&comb, None, &TriangulationOptions::default())
+                    .unwrap()
+                    .len()
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+use crate::pipeline::Origin;
+use crate::triangulation::TriangulationError;
+
+#[derive(Debug)]
+pub enum Error {
+    Decode(image::ImageError),
+    EmptyMask,
+    ImageTooLarge { width: u32, height: u32 },
+    DegenerateContour { len: usize },
+    Triangulation(TriangulationError),
+    UnbridgedHole { index: usize },
+    InvalidTileSize { width: u32, height: u32 },
+    UnsupportedOrigin(Origin),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Decode(error) => write!(f, "failed to decode image: {}", error),
+            Error::EmptyMask => write!(f, "image has no opaque pixels"),
+            Error::ImageTooLarge { width, height } => {
+                write!(f, "image of {}x{} pixels is too large", width, height)
+            }
+            Error::DegenerateContour { len } => {
+                write!(
+                    f,
+                    "contour has only {} point(s), at least 3 are required",
+                    len
+                )
+            }
+            Error::Triangulation(error) => write!(f, "failed to triangulate: {}", error),
+            Error::UnbridgedHole { index } => {
+                write!(
+                    f,
+                    "hole {} can't be joined to the outer ring without crossing an edge",
+                    index
+                )
+            }
+            Error::InvalidTileSize { width, height } => {
+                write!(f, "tiles of {}x{} pixels have no area", width, height)
+            }
+            Error::UnsupportedOrigin(origin) => {
+                write!(f, "origin {:?} is not supported, use TopLeft", origin)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(error) => Some(error),
+            Error::Triangulation(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(error: image::ImageError) -> Self {
+        Error::Decode(error)
+    }
+}
+
+impl From<TriangulationError> for Error {
+    fn from(error: TriangulationError) -> Self {
+        Error::Triangulation(error)
+    }
+}
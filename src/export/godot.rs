@@ -0,0 +1,263 @@
+//! Godot 4 resources and scenes, in the text formats of `.tres` and `.tscn` files.
+//!
+//! Godot has the y axis pointing down like images, so points only need to be moved relative to
+//! the pivot of the sprite. The default pivot is the top-left corner of the image, the origin of a
+//! `Sprite2D` that isn't centered; use [`GodotOptions::centered`] for the default, centered one.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::{bridge_holes, number};
+use crate::geometry::*;
+use crate::pipeline::{Hitbox, Origin};
+use crate::Error;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GodotOptions {
+    /// Point of the image placed at the origin of the node.
+    pub pivot: PointF,
+    /// Winding of the polygons written, and of the outer rings before their holes are bridged.
+    pub winding: Winding,
+    /// Name of the root node of scenes.
+    pub root_name: String,
+    /// Type of the root node of scenes, a `CollisionObject2D` such as `StaticBody2D` or `Area2D`.
+    pub root_type: String,
+}
+
+impl Default for GodotOptions {
+    fn default() -> Self {
+        Self {
+            pivot: PointF::default(),
+            winding: Winding::Clockwise,
+            root_name: "Hitbox".to_string(),
+            root_type: "StaticBody2D".to_string(),
+        }
+    }
+}
+
+impl GodotOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pivot(mut self, pivot: PointF) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Puts the pivot at the center of an image of the given size, where a centered `Sprite2D`
+    /// has its origin.
+    pub fn centered(self, width: u32, height: u32) -> Self {
+        self.pivot(PointF::new(width as f64 / 2.0, height as f64 / 2.0))
+    }
+
+    pub fn winding(mut self, winding: Winding) -> Self {
+        self.winding = winding;
+        self
+    }
+
+    pub fn root(mut self, name: &str, node_type: &str) -> Self {
+        self.root_name = name.to_string();
+        self.root_type = node_type.to_string();
+        self
+    }
+}
+
+fn string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn packed_vector2_array<P: Vertex>(ring: &Polygon<P>, options: &GodotOptions) -> String {
+    let coordinates: Vec<String> = ring
+        .clone()
+        .with_winding(options.winding)
+        .iter()
+        .flat_map(|&point| {
+            let point = point.into() - options.pivot;
+            [number(point.x), number(point.y)]
+        })
+        .collect();
+    format!("PackedVector2Array({})", coordinates.join(", "))
+}
+
+// Name of the `index`-th of several sibling nodes, numbered like the Godot editor does.
+fn node_name(base: &str, index: usize) -> String {
+    match index {
+        0 => base.to_string(),
+        _ => format!("{}{}", base, index + 1),
+    }
+}
+
+fn scene_header(output: &mut String, sub_resources: usize) {
+    match sub_resources {
+        0 => output.push_str("[gd_scene format=3]\n\n"),
+        _ => {
+            let _ = write!(
+                output,
+                "[gd_scene load_steps={} format=3]\n\n",
+                sub_resources + 1
+            );
+        }
+    }
+}
+
+fn root_node(output: &mut String, options: &GodotOptions) {
+    let _ = writeln!(
+        output,
+        "[node name={} type={}]",
+        string(&options.root_name),
+        string(&options.root_type)
+    );
+}
+
+/// A `ConvexPolygonShape2D` resource holding one convex piece.
+pub fn convex_polygon_shape<P: Vertex>(piece: &Polygon<P>, options: &GodotOptions) -> String {
+    format!(
+        "[gd_resource type=\"ConvexPolygonShape2D\" format=3]\n\n[resource]\npoints = {}\n",
+        packed_vector2_array(piece, options)
+    )
+}
+
+/// A scene with one `CollisionShape2D` per convex piece, each with its own
+/// `ConvexPolygonShape2D`.
+pub fn convex_polygon_scene<P: Vertex>(pieces: &[Polygon<P>], options: &GodotOptions) -> String {
+    let mut output = String::new();
+    scene_header(&mut output, pieces.len());
+    for (index, piece) in pieces.iter().enumerate() {
+        let _ = write!(
+            output,
+            "[sub_resource type=\"ConvexPolygonShape2D\" id=\"ConvexPolygonShape2D_{}\"]\npoints = {}\n\n",
+            index + 1,
+            packed_vector2_array(piece, options)
+        );
+    }
+    root_node(&mut output, options);
+    for index in 0..pieces.len() {
+        let _ = write!(
+            output,
+            "\n[node name={} type=\"CollisionShape2D\" parent=\".\"]\nshape = SubResource(\"ConvexPolygonShape2D_{}\")\n",
+            string(&node_name("CollisionShape2D", index)),
+            index + 1
+        );
+    }
+    output
+}
+
+/// A scene with one `CollisionPolygon2D` per shape. `CollisionPolygon2D` has no holes, so they
+/// are bridged to the outer ring with [`bridge_holes`], whose error is returned.
+pub fn collision_polygon_scene<P: Vertex>(
+    shapes: &[Shape<P>],
+    options: &GodotOptions,
+) -> Result<String, Error> {
+    let mut output = String::new();
+    scene_header(&mut output, 0);
+    root_node(&mut output, options);
+    for (index, shape) in shapes.iter().enumerate() {
+        let outer = shape.outer.clone().with_winding(options.winding);
+        let _ = write!(
+            output,
+            "\n[node name={} type=\"CollisionPolygon2D\" parent=\".\"]\npolygon = {}\n",
+            string(&node_name("CollisionPolygon2D", index)),
+            packed_vector2_array(&bridge_holes(&outer, &shape.holes)?, options)
+        );
+    }
+    Ok(output)
+}
+
+/// The scene matching the output of a [`Pipeline`](crate::pipeline::Pipeline): triangles and
+/// convex pieces as in [`convex_polygon_scene`], polygons as in [`collision_polygon_scene`].
+///
+/// `origin` is the one the hitbox was made with. Only [`Origin::TopLeft`] is supported, others
+/// fail with [`Error::UnsupportedOrigin`]: the pivot of the options places the shapes instead.
+pub fn hitbox_scene(
+    hitbox: &Hitbox,
+    origin: Origin,
+    options: &GodotOptions,
+) -> Result<String, Error> {
+    if origin != Origin::TopLeft {
+        return Err(Error::UnsupportedOrigin(origin));
+    }
+    match hitbox {
+        Hitbox::Triangles(triangles) => {
+            let pieces: Vec<Polygon<PointF>> = triangles
+                .iter()
+                .map(|&triangle| Polygon::from(triangle.vertices().to_vec()))
+                .collect();
+            Ok(convex_polygon_scene(&pieces, options))
+        }
+        Hitbox::ConvexPieces(pieces) => Ok(convex_polygon_scene(pieces, options)),
+        Hitbox::Polygons(shapes) => collision_polygon_scene(shapes, options),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_polygon_shape() {
+        // Counter-clockwise on screen, written clockwise around the center of a 10x10 image
+        let piece = points(&[(0, 0), (0, 10), (10, 10), (10, 0)]);
+        let options = GodotOptions::new().centered(10, 10);
+
+        assert_eq!(
+            convex_polygon_shape(&piece, &options),
+            "[gd_resource type=\"ConvexPolygonShape2D\" format=3]\n\n[resource]\n\
+             points = PackedVector2Array(5, -5, 5, 5, -5, 5, -5, -5)\n"
+        );
+    }
+
+    #[test]
+    fn test_scenes() {
+        let options = GodotOptions::new().root("Player", "Area2D");
+        let pieces = [
+            points(&[(0, 0), (4, 0), (0, 4)]),
+            points(&[(4, 0), (4, 4), (0, 4)]),
+        ];
+
+        assert_eq!(
+            convex_polygon_scene(&pieces, &options),
+            "[gd_scene load_steps=3 format=3]\n\n\
+             [sub_resource type=\"ConvexPolygonShape2D\" id=\"ConvexPolygonShape2D_1\"]\n\
+             points = PackedVector2Array(0, 0, 4, 0, 0, 4)\n\n\
+             [sub_resource type=\"ConvexPolygonShape2D\" id=\"ConvexPolygonShape2D_2\"]\n\
+             points = PackedVector2Array(4, 0, 4, 4, 0, 4)\n\n\
+             [node name=\"Player\" type=\"Area2D\"]\n\n\
+             [node name=\"CollisionShape2D\" type=\"CollisionShape2D\" parent=\".\"]\n\
+             shape = SubResource(\"ConvexPolygonShape2D_1\")\n\n\
+             [node name=\"CollisionShape2D2\" type=\"CollisionShape2D\" parent=\".\"]\n\
+             shape = SubResource(\"ConvexPolygonShape2D_2\")\n"
+        );
+
+        let shape = Shape {
+            outer: points(&[(0, 0), (10, 0), (10, 10), (0, 10)]),
+            holes: vec![points(&[(4, 4), (6, 4), (6, 6), (4, 6)])],
+        };
+        let scene = collision_polygon_scene(&[shape], &options).unwrap();
+        assert!(scene.starts_with("[gd_scene format=3]\n\n[node name=\"Player\""));
+        assert!(scene.contains(
+            "polygon = PackedVector2Array(0, 0, 10, 0, 6, 4, 4, 4, 4, 6, 6, 6, 6, 4, 10, 0, 10, 10, 0, 10)"
+        ));
+    }
+
+    #[test]
+    fn test_hitbox_scene() {
+        let hitbox = Hitbox::Triangles(vec![TriangleF(
+            PointF::new(0.0, 0.0),
+            PointF::new(4.0, 0.0),
+            PointF::new(0.0, 4.0),
+        )]);
+        let options = GodotOptions::new();
+
+        let scene = hitbox_scene(&hitbox, Origin::TopLeft, &options).unwrap();
+        assert!(scene.contains("points = PackedVector2Array(0, 0, 4, 0, 0, 4)"));
+        for origin in [Origin::Center, Origin::BottomLeft] {
+            assert!(matches!(
+                hitbox_scene(&hitbox, origin, &options),
+                Err(Error::UnsupportedOrigin(error_origin)) if error_origin == origin
+            ));
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Writers for the file formats of game engines and editors.
+
+use crate::geometry::*;
+use crate::Error;
+
+pub mod godot;
+pub mod svg;
+pub mod tiled;
+
+// Text escaped for XML attributes.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Number as written in text formats, without a negative zero.
+fn number(value: f64) -> String {
+    format!("{}", value + 0.0)
+}
+
+/// Merges the holes into the outer ring through zero-width bridges, for formats whose polygons
+/// can't have holes. Each hole is walked in the opposite direction to the outer ring and joined to
+/// the closest point of the ring it can see, so the result keeps the area of the polygon.
+///
+/// Holes with no area are dropped. Fails with [`Error::UnbridgedHole`] when no point of the ring
+/// can be reached from a hole without crossing an edge, which only happens when rings overlap.
+pub fn bridge_holes<P: Vertex>(
+    outer: &Polygon<P>,
+    holes: &[Polygon<P>],
+) -> Result<Polygon<P>, Error> {
+    let mut ring = outer.clone();
+    ring.open();
+    let Some(winding) = ring.winding() else {
+        return Ok(ring);
+    };
+
+    let mut holes: Vec<(usize, Polygon<P>)> = holes
+        .iter()
+        .enumerate()
+        .filter(|(_, hole)| hole.winding().is_some())
+        .map(|(index, hole)| {
+            let mut hole = hole.clone().with_winding(winding.reversed());
+            hole.open();
+            (index, hole)
+        })
+        .collect();
+    // Rightmost holes first, like earcut, so that bridges tend to stay short
+    let rightmost = |hole: &Polygon<P>| {
+        let points = hole.iter().map(|&point| -> PointF { point.into() });
+        let (index, point) = points
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.x.total_cmp(&b.x).then(b.y.total_cmp(&a.y)))
+            .expect("holes have points");
+        (index, point)
+    };
+    holes.sort_by(|(_, a), (_, b)| rightmost(b).1.x.total_cmp(&rightmost(a).1.x));
+
+    for (k, (index, hole)) in holes.iter().enumerate() {
+        let (start, from) = rightmost(hole);
+        let edges = |ring: &Polygon<P>| -> Vec<(PointF, PointF)> {
+            ring.iter()
+                .zip(ring.iter().cycle().skip(1))
+                .map(|(&a, &b)| (a.into(), b.into()))
+                .collect()
+        };
+        let obstacles: Vec<(PointF, PointF)> = std::iter::once(&ring)
+            .chain(holes[k..].iter().map(|(_, hole)| hole))
+            .flat_map(edges)
+            .collect();
+
+        let mut candidates: Vec<(usize, PointF)> =
+            ring.iter().map(|&point| point.into()).enumerate().collect();
+        candidates.sort_by(|(_, a), (_, b)| a.distance(from).total_cmp(&b.distance(from)));
+        let visible = candidates.iter().find(|&&(_, to)| {
+            obstacles
+                .iter()
+                .all(|&(a, b)| !segments_cross(from, to, a, b))
+        });
+        let &(target, _) = visible.ok_or(Error::UnbridgedHole { index: *index })?;
+
+        let mut bridged: Vec<P> = ring[..=target].to_vec();
+        bridged.extend(hole[start..].iter().chain(&hole[..=start]));
+        bridged.extend(&ring[target..]);
+        ring = bridged.into();
+    }
+    Ok(ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bridge_holes() {
+        let outer = points(&[(0, 0), (30, 0), (30, 10), (0, 10)]);
+        let holes = [
+            points(&[(2, 2), (2, 8), (8, 8), (8, 2)]),
+            points(&[(20, 2), (28, 2), (28, 8), (20, 8)]),
+        ];
+
+        let bridged = bridge_holes(&outer, &holes).unwrap();
+
+        assert_eq!(bridged.len(), 4 + 2 * (4 + 2));
+        assert_eq!(bridged.winding(), Some(Winding::Clockwise));
+        assert_eq!(
+            bridged.signed_area(),
+            outer.area() - holes[0].area() - holes[1].area()
+        );
+        let edges: Vec<(PointF, PointF)> = bridged
+            .iter()
+            .zip(bridged.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a.into(), b.into()))
+            .collect();
+        assert!(crossing_pairs(&edges).is_empty());
+    }
+
+    #[test]
+    fn test_bridge_hole_behind_notch() {
+        // The vertex closest to the hole, (14, 10), is the tip of a notch behind its left wall
+        let outer = points(&[
+            (0, 0),
+            (12, 0),
+            (12, 20),
+            (13, 20),
+            (14, 10),
+            (13, 0),
+            (30, 0),
+            (30, 30),
+            (0, 30),
+        ]);
+        let hole = points(&[(4, 8), (10, 10), (4, 12)]);
+
+        let bridged = bridge_holes(&outer, std::slice::from_ref(&hole)).unwrap();
+
+        assert_eq!(bridged.signed_area(), outer.area() - hole.area());
+        let edges: Vec<(PointF, PointF)> = bridged
+            .iter()
+            .zip(bridged.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a.into(), b.into()))
+            .collect();
+        assert!(crossing_pairs(&edges).is_empty());
+    }
+
+    #[test]
+    fn test_unbridged_hole() {
+        // A hole outside the outer ring, whose own wall hides the ring from its rightmost point
+        let outer = points(&[(0, 0), (10, 0), (10, 10), (0, 10)]);
+        let holes = [
+            points(&[(2, 2), (4, 2), (4, 4), (2, 4)]),
+            points(&[(20, 5), (12, 30), (11, 30), (11, 0), (12, 0)]),
+        ];
+
+        assert!(matches!(
+            bridge_holes(&outer, &holes),
+            Err(Error::UnbridgedHole { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number(-0.0), "0");
+        assert_eq!(number(12.0), "12");
+        assert_eq!(number(-1.5), "-1.5");
+    }
+}
@@ -0,0 +1,224 @@
+//! SVG rendering of the steps of the pipeline, to see what went wrong with a hitbox.
+//!
+//! Each set of rings or triangles is a separate `<g>` layer with its own style, drawn over the
+//! optional source image in the order they are added. The image covers the square from `(x, y)`
+//! to `(x + 1, y + 1)` for pixel `(x, y)`, like [`ImagePolygon::to_subpixel_shapes`]; outlines
+//! from [`ImagePolygon::to_shapes`] go through the top-left corner of the pixels.
+//!
+//! [`ImagePolygon::to_subpixel_shapes`]: crate::image::ImagePolygon::to_subpixel_shapes
+//! [`ImagePolygon::to_shapes`]: crate::image::ImagePolygon::to_shapes
+
+use std::fmt;
+use std::io::Cursor;
+
+use image::GenericImageView;
+
+use super::{escape, number};
+use crate::geometry::*;
+
+const STYLE: &str = "\
+g { fill: none; stroke-width: 1; vector-effect: non-scaling-stroke; stroke-linejoin: round }
+g * { vector-effect: non-scaling-stroke }
+image { image-rendering: pixelated }
+.outer { stroke: #1f77b4 }
+.holes { stroke: #d62728 }
+.simplified { stroke: #2ca02c; stroke-dasharray: 4 2 }
+.triangles { fill: #ff7f0e; fill-opacity: 0.2; stroke: #ff7f0e; stroke-opacity: 0.6 }";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3f;
+                output.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+fn points<P: Vertex>(ring: impl IntoIterator<Item = P>) -> String {
+    let points: Vec<String> = ring
+        .into_iter()
+        .map(|point| {
+            let point: PointF = point.into();
+            format!("{},{}", number(point.x), number(point.y))
+        })
+        .collect();
+    points.join(" ")
+}
+
+/// An SVG document built layer by layer, written out with its [`Display`](fmt::Display)
+/// implementation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svg {
+    width: u32,
+    height: u32,
+    image: Option<String>,
+    layers: Vec<String>,
+}
+
+impl Svg {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            image: None,
+            layers: vec![],
+        }
+    }
+
+    /// A document the size of the image, with the image embedded underneath the layers as a PNG.
+    pub fn with_image(image: &image::DynamicImage) -> Self {
+        let (width, height) = image.dimensions();
+        let mut png = Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(image.to_rgba8())
+            .write_to(&mut png, image::ImageFormat::Png)
+            .expect("RGBA images can be written as PNG");
+        Self {
+            image: Some(base64(png.get_ref())),
+            ..Self::new(width, height)
+        }
+    }
+
+    /// Adds a layer with one `<polygon>` per ring.
+    pub fn rings<'a, P: Vertex + 'a>(
+        mut self,
+        class: &str,
+        rings: impl IntoIterator<Item = &'a Polygon<P>>,
+    ) -> Self {
+        let mut layer = format!("<g class=\"{}\">\n", escape(class));
+        for ring in rings {
+            layer.push_str(&format!(
+                "<polygon points=\"{}\"/>\n",
+                points(ring.iter().copied())
+            ));
+        }
+        layer.push_str("</g>\n");
+        self.layers.push(layer);
+        self
+    }
+
+    /// Adds the outer rings of the shapes as the `outer` layer, then their holes as the `holes`
+    /// layer.
+    pub fn shapes<P: Vertex>(self, shapes: &[Shape<P>]) -> Self {
+        self.rings("outer", shapes.iter().map(|shape| &shape.outer))
+            .rings("holes", shapes.iter().flat_map(|shape| &shape.holes))
+    }
+
+    /// Adds every ring of the shapes, simplified ones for instance, as the `simplified` layer.
+    pub fn simplified<P: Vertex>(self, shapes: &[Shape<P>]) -> Self {
+        let rings = shapes
+            .iter()
+            .flat_map(|shape| std::iter::once(&shape.outer).chain(&shape.holes));
+        self.rings("simplified", rings)
+    }
+
+    /// Adds the triangles as the `triangles` layer.
+    pub fn triangles<T: Face>(mut self, triangles: &[T]) -> Self {
+        let mut layer = String::from("<g class=\"triangles\">\n");
+        for &triangle in triangles {
+            layer.push_str(&format!(
+                "<polygon points=\"{}\"/>\n",
+                points(triangle.vertices())
+            ));
+        }
+        layer.push_str("</g>\n");
+        self.layers.push(layer);
+        self
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+            self.width, self.height
+        )?;
+        writeln!(f, "<style>\n{}\n</style>", STYLE)?;
+        if let Some(image) = &self.image {
+            writeln!(
+                f,
+                "<image width=\"{}\" height=\"{}\" href=\"data:image/png;base64,{}\"/>",
+                self.width, self.height, image
+            )?;
+        }
+        for layer in &self.layers {
+            f.write_str(layer)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        let encoded: Vec<String> = ["", "f", "fo", "foo", "foob", "fooba", "foobar"]
+            .iter()
+            .map(|text| base64(text.as_bytes()))
+            .collect();
+        assert_eq!(
+            encoded,
+            ["", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy"]
+        );
+        assert_eq!(base64(&[0xff, 0xfe, 0x00]), "//4A");
+    }
+
+    #[test]
+    fn test_layers() {
+        let shape = Shape {
+            outer: Polygon::from(vec![
+                Point::new(0, 0),
+                Point::new(10, 0),
+                Point::new(10, 10),
+                Point::new(0, 10),
+            ]),
+            holes: vec![Polygon::from(vec![
+                Point::new(4, 4),
+                Point::new(4, 6),
+                Point::new(6, 4),
+            ])],
+        };
+        let triangle = Triangle(Point::new(0, 0), Point::new(10, 0), Point::new(0, 10));
+
+        let svg = Svg::new(12, 12)
+            .shapes(std::slice::from_ref(&shape))
+            .triangles(&[triangle])
+            .to_string();
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"12\" height=\"12\"")
+        );
+        assert!(svg.contains(
+            "<g class=\"outer\">\n<polygon points=\"0,0 10,0 10,10 0,10\"/>\n</g>\n\
+             <g class=\"holes\">\n<polygon points=\"4,4 4,6 6,4\"/>\n</g>\n\
+             <g class=\"triangles\">\n<polygon points=\"0,0 10,0 0,10\"/>\n</g>\n</svg>\n"
+        ));
+        assert!(!svg.contains("<image"));
+    }
+
+    #[test]
+    fn test_embedded_image() {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::new(3, 2));
+        let svg = Svg::with_image(&image).to_string();
+
+        let start = svg.find("base64,").unwrap() + "base64,".len();
+        let encoded = &svg[start..start + svg[start..].find('"').unwrap()];
+        // The PNG signature
+        assert!(encoded.starts_with("iVBORw0KGgo"));
+        assert!(svg.contains("<image width=\"3\" height=\"2\""));
+    }
+}
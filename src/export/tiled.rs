@@ -0,0 +1,403 @@
+//! Tiled tilesets and object layers, as XML (`.tsx`, `.tmx`) or JSON (`.tsj`, `.tmj`).
+//!
+//! Tiled polygons have no holes, so they are bridged to the outer ring with [`bridge_holes`].
+//! Each polygon object is placed at its first point, with its points relative to it like Tiled
+//! does.
+
+use std::fmt::Write;
+
+use image::GenericImageView;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{bridge_holes, escape, number};
+use crate::geometry::*;
+use crate::pipeline::{Origin, Pipeline};
+use crate::Error;
+
+// Version of the file format, and of the Tiled release it comes with.
+const VERSION: &str = "1.10";
+const TILED_VERSION: &str = "1.10.2";
+
+/// The shapes of one tile of a tileset.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tile<P = Point> {
+    /// Index of the tile, counted row by row from the top-left one.
+    pub id: u32,
+    pub shapes: Vec<Shape<P>>,
+}
+
+/// A tileset made of a single spritesheet image.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tileset {
+    pub name: String,
+    /// Path of the image, relative to the tileset file.
+    pub image: String,
+    pub image_width: u32,
+    pub image_height: u32,
+    pub tile_width: u32,
+    pub tile_height: u32,
+}
+
+impl Tileset {
+    pub fn columns(&self) -> u32 {
+        self.image_width / self.tile_width.max(1)
+    }
+
+    pub fn tile_count(&self) -> u32 {
+        self.columns() * (self.image_height / self.tile_height.max(1))
+    }
+}
+
+type Object = (PointF, Vec<PointF>);
+
+// The bridged rings of the shapes, each with the position of its object.
+fn objects<P: Vertex>(shapes: &[Shape<P>], offset: PointF) -> Result<Vec<Object>, Error> {
+    let mut objects = vec![];
+    for shape in shapes {
+        let ring = bridge_holes(&shape.outer, &shape.holes)?;
+        let points: Vec<PointF> = ring.iter().map(|&point| point.into()).collect();
+        if let Some(&origin) = points.first() {
+            let relative = points.iter().map(|&point| point - origin).collect();
+            objects.push((origin + offset, relative));
+        }
+    }
+    Ok(objects)
+}
+
+fn write_objects_xml(output: &mut String, objects: &[Object], first_id: u32, indent: &str) {
+    for (id, (position, points)) in (first_id..).zip(objects) {
+        let points: Vec<String> = points
+            .iter()
+            .map(|point| format!("{},{}", number(point.x), number(point.y)))
+            .collect();
+        let _ = writeln!(
+            output,
+            "{indent}<object id=\"{}\" x=\"{}\" y=\"{}\">\n{indent} <polygon points=\"{}\"/>\n{indent}</object>",
+            id,
+            number(position.x),
+            number(position.y),
+            points.join(" ")
+        );
+    }
+}
+
+fn objects_json(objects: &[Object], first_id: u32) -> Vec<Value> {
+    (first_id..)
+        .zip(objects)
+        .map(|(id, (position, points))| {
+            let polygon: Vec<Value> = points
+                .iter()
+                .map(|point| json!({ "x": point.x + 0.0, "y": point.y + 0.0 }))
+                .collect();
+            json!({
+                "id": id,
+                "name": "",
+                "type": "",
+                "x": position.x + 0.0,
+                "y": position.y + 0.0,
+                "width": 0,
+                "height": 0,
+                "rotation": 0,
+                "visible": true,
+                "polygon": polygon,
+            })
+        })
+        .collect()
+}
+
+/// A `.tsx` tileset whose tiles have their shapes as collision polygons. Fails when the holes of a
+/// shape can't be bridged.
+pub fn tsx<P: Vertex>(tileset: &Tileset, tiles: &[Tile<P>]) -> Result<String, Error> {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        output,
+        "<tileset version=\"{}\" tiledversion=\"{}\" name=\"{}\" tilewidth=\"{}\" tileheight=\"{}\" tilecount=\"{}\" columns=\"{}\">",
+        VERSION,
+        TILED_VERSION,
+        escape(&tileset.name),
+        tileset.tile_width,
+        tileset.tile_height,
+        tileset.tile_count(),
+        tileset.columns()
+    );
+    let _ = writeln!(
+        output,
+        " <image source=\"{}\" width=\"{}\" height=\"{}\"/>",
+        escape(&tileset.image),
+        tileset.image_width,
+        tileset.image_height
+    );
+    for tile in tiles.iter().filter(|tile| !tile.shapes.is_empty()) {
+        let _ = writeln!(
+            output,
+            " <tile id=\"{}\">\n  <objectgroup draworder=\"index\">",
+            tile.id
+        );
+        let objects = objects(&tile.shapes, PointF::default())?;
+        write_objects_xml(&mut output, &objects, 1, "   ");
+        output.push_str("  </objectgroup>\n </tile>\n");
+    }
+    output.push_str("</tileset>\n");
+    Ok(output)
+}
+
+/// The JSON equivalent of [`tsx`], a `.tsj` tileset.
+pub fn tsj<P: Vertex>(tileset: &Tileset, tiles: &[Tile<P>]) -> Result<String, Error> {
+    let tiles: Vec<Value> = tiles
+        .iter()
+        .filter(|tile| !tile.shapes.is_empty())
+        .map(|tile| {
+            Ok(json!({
+                "id": tile.id,
+                "objectgroup": {
+                    "type": "objectgroup",
+                    "name": "",
+                    "draworder": "index",
+                    "opacity": 1,
+                    "visible": true,
+                    "x": 0,
+                    "y": 0,
+                    "objects": objects_json(&objects(&tile.shapes, PointF::default())?, 1),
+                },
+            }))
+        })
+        .collect::<Result<_, Error>>()?;
+    let tileset = json!({
+        "type": "tileset",
+        "version": VERSION,
+        "tiledversion": TILED_VERSION,
+        "name": tileset.name,
+        "tilewidth": tileset.tile_width,
+        "tileheight": tileset.tile_height,
+        "tilecount": tileset.tile_count(),
+        "columns": tileset.columns(),
+        "margin": 0,
+        "spacing": 0,
+        "image": tileset.image,
+        "imagewidth": tileset.image_width,
+        "imageheight": tileset.image_height,
+        "tiles": tiles,
+    });
+    Ok(serde_json::to_string_pretty(&tileset).expect("JSON values serialize"))
+}
+
+/// An `<objectgroup>` layer of a `.tmx` map, with the shapes moved by `offset` and object ids
+/// starting at `first_object_id`. Fails like [`tsx`].
+pub fn object_group_xml<P: Vertex>(
+    id: u32,
+    name: &str,
+    shapes: &[Shape<P>],
+    offset: PointF,
+    first_object_id: u32,
+) -> Result<String, Error> {
+    let mut output = format!(" <objectgroup id=\"{}\" name=\"{}\">\n", id, escape(name));
+    write_objects_xml(
+        &mut output,
+        &objects(shapes, offset)?,
+        first_object_id,
+        "  ",
+    );
+    output.push_str(" </objectgroup>\n");
+    Ok(output)
+}
+
+/// The JSON equivalent of [`object_group_xml`], a layer of a `.tmj` map.
+pub fn object_group_json<P: Vertex>(
+    id: u32,
+    name: &str,
+    shapes: &[Shape<P>],
+    offset: PointF,
+    first_object_id: u32,
+) -> Result<String, Error> {
+    let layer = json!({
+        "id": id,
+        "name": name,
+        "type": "objectgroup",
+        "draworder": "topdown",
+        "opacity": 1,
+        "visible": true,
+        "x": 0,
+        "y": 0,
+        "objects": objects_json(&objects(shapes, offset)?, first_object_id),
+    });
+    Ok(serde_json::to_string_pretty(&layer).expect("JSON values serialize"))
+}
+
+/// Runs the pipeline on every tile of a spritesheet, row by row. Tiles without any solid pixel,
+/// or with only specks too small to outline, have no shapes. Fails with [`Error::InvalidTileSize`] when a tile dimension is zero, and with
+/// [`Error::UnsupportedOrigin`] unless the origin of the pipeline is [`Origin::TopLeft`], the one
+/// of Tiled.
+pub fn spritesheet_tiles(
+    image: &image::DynamicImage,
+    tile_width: u32,
+    tile_height: u32,
+    pipeline: &Pipeline,
+) -> Result<Vec<Tile<PointF>>, Error> {
+    if tile_width == 0 || tile_height == 0 {
+        return Err(Error::InvalidTileSize {
+            width: tile_width,
+            height: tile_height,
+        });
+    }
+    match pipeline.options().origin {
+        Origin::TopLeft => {}
+        origin => return Err(Error::UnsupportedOrigin(origin)),
+    }
+
+    let (width, height) = image.dimensions();
+    let (columns, rows) = (width / tile_width, height / tile_height);
+    let mut tiles = vec![];
+    for (id, (row, column)) in
+        (0..).zip((0..rows).flat_map(|row| (0..columns).map(move |column| (row, column))))
+    {
+        let tile = image.crop_imm(
+            column * tile_width,
+            row * tile_height,
+            tile_width,
+            tile_height,
+        );
+        let shapes = match pipeline.shapes(tile) {
+            Err(Error::EmptyMask | Error::DegenerateContour { .. }) => vec![],
+            shapes => shapes?,
+        };
+        tiles.push(Tile { id, shapes });
+    }
+    Ok(tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::PipelineOptions;
+
+    fn tileset() -> Tileset {
+        Tileset {
+            name: "Sprites & co".to_string(),
+            image: "sprites.png".to_string(),
+            image_width: 64,
+            image_height: 32,
+            tile_width: 32,
+            tile_height: 32,
+        }
+    }
+
+    fn tiles() -> Vec<Tile> {
+        let shape = Shape {
+            outer: points(&[(2, 2), (10, 2), (10, 8)]),
+            holes: vec![],
+        };
+        vec![
+            Tile {
+                id: 0,
+                shapes: vec![],
+            },
+            Tile {
+                id: 1,
+                shapes: vec![shape],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_tsx() {
+        assert_eq!(
+            tsx(&tileset(), &tiles()).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <tileset version=\"1.10\" tiledversion=\"1.10.2\" name=\"Sprites &amp; co\" tilewidth=\"32\" tileheight=\"32\" tilecount=\"2\" columns=\"2\">\n \
+             <image source=\"sprites.png\" width=\"64\" height=\"32\"/>\n \
+             <tile id=\"1\">\n  \
+             <objectgroup draworder=\"index\">\n   \
+             <object id=\"1\" x=\"2\" y=\"2\">\n    \
+             <polygon points=\"0,0 8,0 8,6\"/>\n   \
+             </object>\n  \
+             </objectgroup>\n \
+             </tile>\n\
+             </tileset>\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let tileset: Value = serde_json::from_str(&tsj(&tileset(), &tiles()).unwrap()).unwrap();
+        assert_eq!(tileset["tilecount"], 2);
+        assert_eq!(tileset["tiledversion"], "1.10.2");
+        assert_eq!(tileset["tiles"].as_array().unwrap().len(), 1);
+        let object = &tileset["tiles"][0]["objectgroup"]["objects"][0];
+        assert_eq!(
+            (object["x"].as_f64(), object["y"].as_f64()),
+            (Some(2.0), Some(2.0))
+        );
+        assert_eq!(object["polygon"][1], json!({ "x": 8.0, "y": 0.0 }));
+
+        let shapes = &tiles()[1].shapes;
+        let layer: Value = serde_json::from_str(
+            &object_group_json(3, "Hitboxes", shapes, PointF::new(32.0, 0.0), 7).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(layer["objects"][0]["id"], 7);
+        assert_eq!(layer["objects"][0]["x"].as_f64(), Some(34.0));
+        assert!(
+            object_group_xml(3, "Hitboxes", shapes, PointF::new(32.0, 0.0), 7)
+                .unwrap()
+                .contains("<object id=\"7\" x=\"34\" y=\"2\">")
+        );
+    }
+
+    #[test]
+    fn test_spritesheet_tiles() {
+        // Two 4x4 tiles, the first one empty and the second one with a 2x2 square
+        let mut image = image::RgbaImage::new(8, 4);
+        for y in 1..3 {
+            for x in 5..7 {
+                image.put_pixel(x, y, image::Rgba([0, 0, 0, 255]));
+            }
+        }
+        let pipeline = Pipeline::new(PipelineOptions::new().subpixel(true));
+
+        let tiles =
+            spritesheet_tiles(&image::DynamicImage::ImageRgba8(image), 4, 4, &pipeline).unwrap();
+
+        assert_eq!(tiles.len(), 2);
+        assert!(tiles[0].shapes.is_empty());
+        let bounds = tiles[1].shapes[0].outer.bounding_box().unwrap();
+        assert!(bounds.min.x >= 0.5 && bounds.max.x <= 3.5);
+    }
+
+    #[test]
+    fn test_spritesheet_speck() {
+        // A stray pixel in the first tile, which pixel outlines can't make a polygon of
+        let mut image = image::RgbaImage::new(8, 4);
+        image.put_pixel(1, 1, image::Rgba([0, 0, 0, 255]));
+        for y in 1..3 {
+            for x in 5..7 {
+                image.put_pixel(x, y, image::Rgba([0, 0, 0, 255]));
+            }
+        }
+        let pipeline = Pipeline::new(PipelineOptions::new());
+
+        let tiles =
+            spritesheet_tiles(&image::DynamicImage::ImageRgba8(image), 4, 4, &pipeline).unwrap();
+
+        assert!(tiles[0].shapes.is_empty());
+        assert_eq!(tiles[1].shapes.len(), 1);
+    }
+
+    #[test]
+    fn test_spritesheet_tiles_errors() {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::new(8, 4));
+
+        assert!(matches!(
+            spritesheet_tiles(&image, 0, 4, &Pipeline::new(PipelineOptions::new())),
+            Err(Error::InvalidTileSize {
+                width: 0,
+                height: 4
+            })
+        ));
+        let centered = Pipeline::new(PipelineOptions::new().origin(Origin::Center));
+        assert!(matches!(
+            spritesheet_tiles(&image, 4, 4, &centered),
+            Err(Error::UnsupportedOrigin(Origin::Center))
+        ));
+    }
+}
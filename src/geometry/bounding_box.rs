@@ -0,0 +1,56 @@
+use super::PointF;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub min: PointF,
+    pub max: PointF,
+}
+
+impl BoundingBox {
+    /// Returns the smallest box containing every point, or `None` when there is none.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<PointF>,
+    {
+        let mut points = points.into_iter().map(Into::into);
+        let first: PointF = points.next()?;
+        Some(points.fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |bounding_box, point| Self {
+                min: PointF::new(
+                    bounding_box.min.x.min(point.x),
+                    bounding_box.min.y.min(point.y),
+                ),
+                max: PointF::new(
+                    bounding_box.max.x.max(point.x),
+                    bounding_box.max.y.max(point.y),
+                ),
+            },
+        ))
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    pub fn contains(&self, point: PointF) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Whether the two boxes overlap, touching sides included.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
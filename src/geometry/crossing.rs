@@ -0,0 +1,100 @@
+use super::PointF;
+
+/// Whether the segments `a b` and `c d` cross at a single point inside both of them. Segments
+/// that only touch, or that overlap along a line, don't cross.
+pub(crate) fn segments_cross(a: PointF, b: PointF, c: PointF, d: PointF) -> bool {
+    let orient = |p: PointF, q: PointF, r: PointF| (q - p).cross(r - p);
+    let opposite = |x: f64, y: f64| (x > 0.0 && y < 0.0) || (x < 0.0 && y > 0.0);
+    opposite(orient(a, b, c), orient(a, b, d)) && opposite(orient(c, d, a), orient(c, d, b))
+}
+
+// Sweeps the segments along the x axis and calls `found` with the indices of every crossing
+// pair, stopping as soon as it returns `true`.
+fn sweep(segments: &[(PointF, PointF)], mut found: impl FnMut(usize, usize) -> bool) {
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&i, &j| {
+        let ((a, b), (c, d)) = (segments[i], segments[j]);
+        a.x.min(b.x).total_cmp(&c.x.min(d.x))
+    });
+
+    let mut active: Vec<usize> = vec![];
+    for i in order {
+        let (a, b) = segments[i];
+        let start = a.x.min(b.x);
+        active.retain(|&j| {
+            let (c, d) = segments[j];
+            c.x.max(d.x) >= start
+        });
+        for &j in &active {
+            let (c, d) = segments[j];
+            if segments_cross(a, b, c, d) && found(j.min(i), j.max(i)) {
+                return;
+            }
+        }
+        active.push(i);
+    }
+}
+
+/// Every pair of crossing segments, as indices into `segments`.
+pub(crate) fn crossing_pairs(segments: &[(PointF, PointF)]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    sweep(segments, |i, j| {
+        pairs.push((i, j));
+        false
+    });
+    pairs
+}
+
+/// Finds two crossing edges among closed rings, and returns the indices of their rings, the
+/// smaller one first.
+pub(crate) fn first_crossing(rings: &[Vec<PointF>]) -> Option<(usize, usize)> {
+    let (rings_of, segments): (Vec<usize>, Vec<(PointF, PointF)>) = rings
+        .iter()
+        .enumerate()
+        .flat_map(|(ring, points)| {
+            points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(move |(&a, &b)| (ring, (a, b)))
+        })
+        .unzip();
+
+    let mut crossing = None;
+    sweep(&segments, |i, j| {
+        let (first, second) = (rings_of[i], rings_of[j]);
+        crossing = Some((first.min(second), first.max(second)));
+        true
+    });
+    crossing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_crossing() {
+        let square = vec![
+            PointF::new(0.0, 0.0),
+            PointF::new(4.0, 0.0),
+            PointF::new(4.0, 4.0),
+            PointF::new(0.0, 4.0),
+        ];
+        let bow_tie = vec![
+            PointF::new(0.0, 0.0),
+            PointF::new(4.0, 4.0),
+            PointF::new(4.0, 0.0),
+            PointF::new(0.0, 4.0),
+        ];
+        let touching = vec![
+            PointF::new(4.0, 4.0),
+            PointF::new(6.0, 4.0),
+            PointF::new(6.0, 6.0),
+        ];
+
+        assert_eq!(first_crossing(&[square.clone(), touching.clone()]), None);
+        assert_eq!(first_crossing(&[bow_tie]), Some((0, 0)));
+        let shifted = square.iter().map(|&p| p + PointF::new(2.0, 1.0)).collect();
+        assert_eq!(first_crossing(&[touching, square, shifted]), Some((1, 2)));
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Face, Point, PointF, Vertex};
+
+/// An indexed triangle mesh: every vertex is stored once and triangles refer to it through
+/// `indices`, three per triangle, wound clockwise.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Mesh<P = Point> {
+    pub vertices: Vec<P>,
+    pub indices: Vec<u32>,
+    /// `adjacency[t][k]` is the triangle on the other side of the edge going from the `k`-th to
+    /// the next vertex of triangle `t`, if any.
+    pub adjacency: Vec<[Option<u32>; 3]>,
+}
+
+impl<P: Vertex> Mesh<P> {
+    /// Builds a mesh from a vertex buffer and triangle indices into it. Vertices with the same
+    /// coordinates are merged, and triangles left with a repeated vertex are dropped.
+    pub fn new(vertices: Vec<P>, indices: &[usize]) -> Self {
+        let coordinates: Vec<PointF> = vertices.iter().map(|&vertex| vertex.into()).collect();
+        let mut order: Vec<usize> = (0..vertices.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (coordinates[a], coordinates[b]);
+            a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+        });
+
+        // Each vertex points to the first one with the same coordinates
+        let mut first: Vec<usize> = (0..vertices.len()).collect();
+        for run in order.chunk_by(|&a, &b| coordinates[a] == coordinates[b]) {
+            let representative = run.iter().copied().min().unwrap_or_default();
+            for &vertex in run {
+                first[vertex] = representative;
+            }
+        }
+
+        let mut remap = vec![0; vertices.len()];
+        let mut kept = vec![];
+        for (vertex, &representative) in first.iter().enumerate() {
+            if representative == vertex {
+                remap[vertex] = kept.len() as u32;
+                kept.push(vertices[vertex]);
+            } else {
+                remap[vertex] = remap[representative];
+            }
+        }
+
+        let mut mesh_indices = Vec::with_capacity(indices.len());
+        for triangle in indices.chunks_exact(3) {
+            let mut triangle = [remap[triangle[0]], remap[triangle[1]], remap[triangle[2]]];
+            let [a, b, c] = triangle;
+            if a == b || b == c || c == a {
+                continue;
+            }
+            let [a, b, c] = triangle.map(|index| kept[index as usize].into());
+            if (b - a).cross(c - a) < 0.0 {
+                triangle.swap(1, 2);
+            }
+            mesh_indices.extend(triangle);
+        }
+
+        let adjacency = adjacency(&mesh_indices);
+        Self {
+            vertices: kept,
+            indices: mesh_indices,
+            adjacency,
+        }
+    }
+
+    /// Builds a mesh from standalone triangles, such as the output of
+    /// [`crate::triangulation::triangulate`].
+    pub fn from_triangles<T: Face<Vertex = P>>(triangles: &[T]) -> Self {
+        let vertices: Vec<P> = triangles
+            .iter()
+            .flat_map(|&triangle| triangle.vertices())
+            .collect();
+        let indices: Vec<usize> = (0..vertices.len()).collect();
+        Self::new(vertices, &indices)
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    pub fn triangle(&self, index: usize) -> P::Triangle {
+        let corners = &self.indices[index * 3..index * 3 + 3];
+        P::Triangle::from([
+            self.vertices[corners[0] as usize],
+            self.vertices[corners[1] as usize],
+            self.vertices[corners[2] as usize],
+        ])
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = P::Triangle> + '_ {
+        (0..self.triangle_count()).map(|index| self.triangle(index))
+    }
+}
+
+fn adjacency(indices: &[u32]) -> Vec<[Option<u32>; 3]> {
+    let mut edges: HashMap<(u32, u32), Vec<(usize, usize)>> = HashMap::new();
+    for (triangle, corners) in indices.chunks_exact(3).enumerate() {
+        for edge in 0..3 {
+            let (a, b) = (corners[edge], corners[(edge + 1) % 3]);
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push((triangle, edge));
+        }
+    }
+
+    let mut adjacency = vec![[None; 3]; indices.len() / 3];
+    for sides in edges.values() {
+        // Edges shared by more than two triangles are non-manifold and left unconnected
+        if let [(t, e), (u, f)] = sides[..] {
+            adjacency[t][e] = Some(u as u32);
+            adjacency[u][f] = Some(t as u32);
+        }
+    }
+    adjacency
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Triangle;
+
+    #[test]
+    fn test_mesh_from_triangles() {
+        let square = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(0, 4),
+        ];
+        let triangles = [
+            Triangle(square[0], square[1], square[2]),
+            Triangle(square[0], square[3], square[2]),
+        ];
+        let mesh = Mesh::from_triangles(&triangles);
+
+        assert_eq!(mesh.vertices, square);
+        assert_eq!(mesh.indices, [0, 1, 2, 0, 2, 3]);
+        assert_eq!(
+            mesh.adjacency,
+            [[None, None, Some(1)], [Some(0), None, None]]
+        );
+        assert_eq!(mesh.triangles().collect::<Vec<_>>(), triangles);
+    }
+
+    #[test]
+    fn test_mesh_drops_degenerate_triangles() {
+        let vertices = vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 0)];
+        let mesh = Mesh::new(vertices, &[0, 1, 2]);
+
+        assert_eq!(mesh.vertices.len(), 2);
+        assert_eq!(mesh.triangle_count(), 0);
+    }
+}
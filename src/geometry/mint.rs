@@ -0,0 +1,159 @@
+use super::{Point, PointF, Polygon, Triangle, TriangleF};
+use ::mint::{Point2, Vector2};
+
+impl From<Point> for Point2<u32> {
+    fn from(point: Point) -> Self {
+        Point2 {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<Point2<u32>> for Point {
+    fn from(point: Point2<u32>) -> Self {
+        Point::new(point.x, point.y)
+    }
+}
+
+impl From<Point> for Vector2<u32> {
+    fn from(point: Point) -> Self {
+        Vector2 {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<Vector2<u32>> for Point {
+    fn from(vector: Vector2<u32>) -> Self {
+        Point::new(vector.x, vector.y)
+    }
+}
+
+impl From<PointF> for Point2<f64> {
+    fn from(point: PointF) -> Self {
+        Point2 {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<Point2<f64>> for PointF {
+    fn from(point: Point2<f64>) -> Self {
+        PointF::new(point.x, point.y)
+    }
+}
+
+impl From<PointF> for Vector2<f64> {
+    fn from(point: PointF) -> Self {
+        Vector2 {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl From<Vector2<f64>> for PointF {
+    fn from(vector: Vector2<f64>) -> Self {
+        PointF::new(vector.x, vector.y)
+    }
+}
+
+// Most game math libraries work in single precision.
+impl From<PointF> for Point2<f32> {
+    fn from(point: PointF) -> Self {
+        Point2 {
+            x: point.x as f32,
+            y: point.y as f32,
+        }
+    }
+}
+
+impl From<Point2<f32>> for PointF {
+    fn from(point: Point2<f32>) -> Self {
+        PointF::new(point.x as f64, point.y as f64)
+    }
+}
+
+impl From<PointF> for Vector2<f32> {
+    fn from(point: PointF) -> Self {
+        Vector2 {
+            x: point.x as f32,
+            y: point.y as f32,
+        }
+    }
+}
+
+impl From<Vector2<f32>> for PointF {
+    fn from(vector: Vector2<f32>) -> Self {
+        PointF::new(vector.x as f64, vector.y as f64)
+    }
+}
+
+impl From<Triangle> for [Point2<u32>; 3] {
+    fn from(triangle: Triangle) -> Self {
+        [triangle.0.into(), triangle.1.into(), triangle.2.into()]
+    }
+}
+
+impl From<[Point2<u32>; 3]> for Triangle {
+    fn from([a, b, c]: [Point2<u32>; 3]) -> Self {
+        Triangle(a.into(), b.into(), c.into())
+    }
+}
+
+impl From<TriangleF> for [Point2<f64>; 3] {
+    fn from(triangle: TriangleF) -> Self {
+        [triangle.0.into(), triangle.1.into(), triangle.2.into()]
+    }
+}
+
+impl From<[Point2<f64>; 3]> for TriangleF {
+    fn from([a, b, c]: [Point2<f64>; 3]) -> Self {
+        TriangleF(a.into(), b.into(), c.into())
+    }
+}
+
+impl From<TriangleF> for [Point2<f32>; 3] {
+    fn from(triangle: TriangleF) -> Self {
+        [triangle.0.into(), triangle.1.into(), triangle.2.into()]
+    }
+}
+
+impl From<[Point2<f32>; 3]> for TriangleF {
+    fn from([a, b, c]: [Point2<f32>; 3]) -> Self {
+        TriangleF(a.into(), b.into(), c.into())
+    }
+}
+
+/// Converts every point of a polygon, e.g. `polygon_to_mint::<_, mint::Point2<f32>>(&polygon)`.
+pub fn polygon_to_mint<P, M>(polygon: &[P]) -> Vec<M>
+where
+    P: Copy + Into<M>,
+{
+    polygon.iter().map(|&point| point.into()).collect()
+}
+
+pub fn polygon_from_mint<M, P>(points: &[M]) -> Polygon<P>
+where
+    M: Copy + Into<P>,
+{
+    points.iter().map(|&point| point.into()).collect()
+}
+
+pub fn triangles_to_mint<T, M>(triangles: &[T]) -> Vec<[M; 3]>
+where
+    T: Copy + Into<[M; 3]>,
+{
+    triangles.iter().map(|&triangle| triangle.into()).collect()
+}
+
+pub fn triangles_from_mint<M, T>(triangles: &[[M; 3]]) -> Vec<T>
+where
+    M: Copy,
+    [M; 3]: Into<T>,
+{
+    triangles.iter().map(|&triangle| triangle.into()).collect()
+}
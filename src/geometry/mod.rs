@@ -3,42 +3,60 @@ use std::cmp::Ordering;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
+mod bounding_box;
+mod crossing;
+mod mesh;
+mod mint;
 mod point;
 mod polygon;
+mod shape;
 mod triangle;
+mod vertex;
 
-pub use point::Point;
-pub use polygon::Polygon;
-pub use triangle::Triangle;
+pub use self::mint::{polygon_from_mint, polygon_to_mint, triangles_from_mint, triangles_to_mint};
+pub use bounding_box::BoundingBox;
+pub(crate) use crossing::{crossing_pairs, first_crossing, segments_cross};
+pub use mesh::Mesh;
+pub use point::{Point, PointF};
+pub use polygon::{Polygon, Winding};
+pub use shape::Shape;
+pub use triangle::{Circle, Containment, Triangle, TriangleF};
+pub use vertex::{Face, Vertex};
 
+/// Distance rounded down, saturating at `u32::MAX` for points on opposite corners of the range.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-pub fn distance(a: Point, b: Point) -> u16 {
-    let dx = (a.x as i32 - b.x as i32).unsigned_abs() as u16;
-    let dy = (a.y as i32 - b.y as i32).unsigned_abs() as u16;
-    (dx as u64 * dx as u64 + dy as u64 * dy as u64).sqrt() as u16
+pub fn distance(a: Point, b: Point) -> u32 {
+    let dx = a.x.abs_diff(b.x) as u128;
+    let dy = a.y.abs_diff(b.y) as u128;
+    u32::try_from((dx * dx + dy * dy).sqrt()).unwrap_or(u32::MAX)
 }
 
-pub fn is_point_inside_triangle(triangle: &Triangle, point: Point) -> bool {
-    let a = triangle.0;
-    let b = triangle.1;
-    let c = triangle.2;
+/// Whether the point lies strictly inside the triangle: points on an edge or a vertex are outside.
+/// Use [`Triangle::contains`] to choose how the boundary is treated.
+pub fn is_point_inside_triangle<P: Vertex>(triangle: &P::Triangle, point: P) -> bool {
+    let [a, b, c]: [P; 3] = (*triangle).into();
+    let [a, b, c, point]: [PointF; 4] = [a.into(), b.into(), c.into(), point.into()];
 
-    let cross_product = |a: Point, b: Point, c: Point| {
-        ((b.x as i32 - a.x as i32) * (c.y as i32 - a.y as i32))
-            - ((b.y as i32 - a.y as i32) * (c.x as i32 - a.x as i32))
-    };
+    let cross_product = |a: PointF, b: PointF, c: PointF| (b - a).cross(c - a);
+    let sign = |value: f64| value.partial_cmp(&0.0).unwrap_or(Ordering::Equal);
 
     matches!(
         (
-            cross_product(a, b, point).cmp(&0),
-            cross_product(b, c, point).cmp(&0),
-            cross_product(c, a, point).cmp(&0),
+            sign(cross_product(a, b, point)),
+            sign(cross_product(b, c, point)),
+            sign(cross_product(c, a, point)),
         ),
         (Ordering::Less, Ordering::Less, Ordering::Less)
             | (Ordering::Greater, Ordering::Greater, Ordering::Greater)
     )
 }
 
+/// A ring of the given points, for tests.
+#[cfg(test)]
+pub(crate) fn points(coordinates: &[(u32, u32)]) -> Polygon {
+    coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,6 +68,60 @@ mod tests {
         assert_eq!(distance(a, b), 5);
     }
 
+    #[test]
+    fn test_distance_large_coordinates() {
+        let a = Point { x: 100_000, y: 0 };
+        let b = Point {
+            x: 400_000,
+            y: 400_000,
+        };
+        assert_eq!(distance(a, b), 500_000);
+    }
+
+    #[test]
+    fn test_distance_full_range() {
+        let a = Point { x: 0, y: 0 };
+        let b = Point {
+            x: 3_500_000_000,
+            y: 0,
+        };
+        let c = Point {
+            x: 3_500_000_000,
+            y: 3_500_000_000,
+        };
+        assert_eq!(distance(a, b), 3_500_000_000);
+        assert_eq!(distance(b, c), 3_500_000_000);
+        assert_eq!(distance(a, c), u32::MAX);
+    }
+
+    #[test]
+    fn test_mint_conversions() {
+        let point = Point { x: 3, y: 4 };
+        let mint_point: ::mint::Point2<u32> = point.into();
+        assert_eq!(mint_point, ::mint::Point2 { x: 3, y: 4 });
+        assert_eq!(Point::from(mint_point), point);
+
+        let vector: ::mint::Vector2<f32> = PointF::from(point).into();
+        assert_eq!(vector, ::mint::Vector2 { x: 3.0, y: 4.0 });
+        assert_eq!(PointF::from(vector), PointF { x: 3.0, y: 4.0 });
+
+        let polygon = vec![
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 4 },
+            Point { x: 4, y: 0 },
+        ];
+        let mint_polygon: Vec<::mint::Point2<u32>> = polygon_to_mint(&polygon);
+        assert_eq!(polygon_from_mint::<_, Point>(&mint_polygon), polygon);
+
+        let triangles = vec![Triangle(polygon[0], polygon[1], polygon[2])];
+        let mint_triangles: Vec<[::mint::Point2<u32>; 3]> = triangles_to_mint(&triangles);
+        assert_eq!(mint_triangles[0][1], ::mint::Point2 { x: 3, y: 4 });
+        assert_eq!(
+            triangles_from_mint::<_, Triangle>(&mint_triangles),
+            triangles
+        );
+    }
+
     #[test]
     fn test_is_point_inside_triangle_on_side() {
         let triangle = Triangle(
@@ -72,6 +144,23 @@ mod tests {
         assert!(is_point_inside_triangle(&triangle, point));
     }
 
+    #[test]
+    fn test_is_point_inside_float_triangle() {
+        let triangle = TriangleF(
+            PointF { x: -1.5, y: 0.0 },
+            PointF { x: 3.0, y: 4.5 },
+            PointF { x: 4.0, y: 0.0 },
+        );
+        assert!(is_point_inside_triangle(
+            &triangle,
+            PointF { x: 0.0, y: 0.5 }
+        ));
+        assert!(!is_point_inside_triangle(
+            &triangle,
+            PointF { x: 0.0, y: 2.0 }
+        ));
+    }
+
     #[test]
     fn test_is_point_outside_triangle() {
         let triangle = Triangle(
@@ -1,18 +1,19 @@
 use serde::{Deserialize, Serialize};
+use std::ops::{Add, Div, Mul, Neg, Sub};
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub struct Point {
-    pub x: u16,
-    pub y: u16,
+    pub x: u32,
+    pub y: u32,
 }
 
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Point {
     #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
-    pub fn new(x: u16, y: u16) -> Self {
+    pub fn new(x: u32, y: u32) -> Self {
         Self { x, y }
     }
 
@@ -48,3 +49,83 @@ impl Point {
         self.left().and_then(|point| point.down())
     }
 }
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default, Serialize, Deserialize)]
+pub struct PointF {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl PointF {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn dot(&self, other: PointF) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn cross(&self, other: PointF) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length(&self) -> f64 {
+        self.dot(*self).sqrt()
+    }
+
+    pub fn distance(&self, other: PointF) -> f64 {
+        (*self - other).length()
+    }
+}
+
+impl From<Point> for PointF {
+    fn from(point: Point) -> Self {
+        Self {
+            x: point.x as f64,
+            y: point.y as f64,
+        }
+    }
+}
+
+impl Add for PointF {
+    type Output = PointF;
+
+    fn add(self, other: PointF) -> PointF {
+        PointF::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for PointF {
+    type Output = PointF;
+
+    fn sub(self, other: PointF) -> PointF {
+        PointF::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for PointF {
+    type Output = PointF;
+
+    fn mul(self, factor: f64) -> PointF {
+        PointF::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Div<f64> for PointF {
+    type Output = PointF;
+
+    fn div(self, divisor: f64) -> PointF {
+        PointF::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl Neg for PointF {
+    type Output = PointF;
+
+    fn neg(self) -> PointF {
+        PointF::new(-self.x, -self.y)
+    }
+}
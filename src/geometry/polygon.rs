@@ -1,3 +1,295 @@
-use super::Point;
+use super::{BoundingBox, Point, PointF, Vertex};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
 
-pub type Polygon = Vec<Point>;
+/// Winding order of a ring, as seen on screen with the y axis pointing down like image
+/// coordinates. A clockwise ring has a positive signed area.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Winding {
+    pub fn reversed(self) -> Self {
+        match self {
+            Winding::Clockwise => Winding::CounterClockwise,
+            Winding::CounterClockwise => Winding::Clockwise,
+        }
+    }
+
+    pub(crate) fn of_signed_area(signed_area: f64) -> Option<Self> {
+        if signed_area > 0.0 {
+            Some(Winding::Clockwise)
+        } else if signed_area < 0.0 {
+            Some(Winding::CounterClockwise)
+        } else {
+            None
+        }
+    }
+}
+
+/// A ring of points. The ring is implicitly closed: the last point connects back to the first one,
+/// which does not need to be repeated.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Polygon<P = Point>(Vec<P>);
+
+impl<P> Polygon<P> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn into_vec(self) -> Vec<P> {
+        self.0
+    }
+}
+
+impl<P: Vertex> Polygon<P> {
+    fn edges(&self) -> impl Iterator<Item = (PointF, PointF)> + '_ {
+        self.iter()
+            .zip(self.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a.into(), b.into()))
+    }
+
+    /// Area given by the shoelace formula, positive for clockwise rings.
+    pub fn signed_area(&self) -> f64 {
+        self.edges().map(|(a, b)| a.cross(b)).sum::<f64>() / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Center of mass of the enclosed surface. Falls back to the average of the points when the
+    /// ring has no area, and returns `None` when it is empty.
+    pub fn centroid(&self) -> Option<PointF> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let signed_area = self.signed_area();
+        if signed_area == 0.0 {
+            let sum = self
+                .iter()
+                .fold(PointF::default(), |sum, &point| sum + point.into());
+            return Some(sum / self.len() as f64);
+        }
+
+        let sum = self
+            .edges()
+            .fold(PointF::default(), |sum, (a, b)| sum + (a + b) * a.cross(b));
+        Some(sum / (6.0 * signed_area))
+    }
+
+    /// Length of the ring, closing edge included.
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.iter().copied())
+    }
+
+    /// Returns `None` when the ring has no area.
+    pub fn winding(&self) -> Option<Winding> {
+        Winding::of_signed_area(self.signed_area())
+    }
+
+    /// Reverses the ring if needed so that it winds in the given order.
+    pub fn set_winding(&mut self, winding: Winding) {
+        if self.winding() == Some(winding.reversed()) {
+            self.reverse();
+        }
+    }
+
+    pub fn with_winding(mut self, winding: Winding) -> Self {
+        self.set_winding(winding);
+        self
+    }
+
+    /// Whether the first point is repeated at the end of the ring.
+    pub fn is_closed(&self) -> bool {
+        self.len() > 1 && self.first() == self.last()
+    }
+
+    /// Repeats the first point at the end of the ring, as some formats expect.
+    pub fn close(&mut self) {
+        if !self.is_closed() {
+            if let Some(&first) = self.first() {
+                self.push(first);
+            }
+        }
+    }
+
+    /// Removes the repeated first point at the end of the ring, if any.
+    pub fn open(&mut self) {
+        if self.is_closed() {
+            self.pop();
+        }
+    }
+
+    /// Even-odd test of whether the point lies inside the ring. Points exactly on the boundary may
+    /// be reported on either side.
+    pub fn contains_point<Q: Into<PointF>>(&self, point: Q) -> bool {
+        let point = point.into();
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+impl<P> Default for Polygon<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Deref for Polygon<P> {
+    type Target = Vec<P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P> DerefMut for Polygon<P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<P> From<Vec<P>> for Polygon<P> {
+    fn from(points: Vec<P>) -> Self {
+        Self(points)
+    }
+}
+
+impl<P: Clone> From<&[P]> for Polygon<P> {
+    fn from(points: &[P]) -> Self {
+        Self(points.to_vec())
+    }
+}
+
+impl<P> From<Polygon<P>> for Vec<P> {
+    fn from(polygon: Polygon<P>) -> Self {
+        polygon.0
+    }
+}
+
+impl From<Polygon<Point>> for Polygon<PointF> {
+    fn from(polygon: Polygon<Point>) -> Self {
+        polygon.into_iter().map(PointF::from).collect()
+    }
+}
+
+impl<P: PartialEq> PartialEq<Vec<P>> for Polygon<P> {
+    fn eq(&self, other: &Vec<P>) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<P> FromIterator<P> for Polygon<P> {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<P> IntoIterator for Polygon<P> {
+    type Item = P;
+    type IntoIter = std::vec::IntoIter<P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, P> IntoIterator for &'a Polygon<P> {
+    type Item = &'a P;
+    type IntoIter = std::slice::Iter<'a, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, P> IntoIterator for &'a mut Polygon<P> {
+    type Item = &'a mut P;
+    type IntoIter = std::slice::IterMut<'a, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l_shape() -> Polygon {
+        Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 4, y: 2 },
+            Point { x: 2, y: 2 },
+            Point { x: 2, y: 4 },
+            Point { x: 0, y: 4 },
+        ])
+    }
+
+    #[test]
+    fn test_area_and_winding() {
+        let mut polygon = l_shape();
+        assert_eq!(polygon.signed_area(), 12.0);
+        assert_eq!(polygon.winding(), Some(Winding::Clockwise));
+
+        polygon.set_winding(Winding::CounterClockwise);
+        assert_eq!(polygon.signed_area(), -12.0);
+        assert_eq!(polygon.area(), 12.0);
+        assert_eq!(polygon[0], Point { x: 0, y: 4 });
+    }
+
+    #[test]
+    fn test_centroid_and_perimeter() {
+        let polygon = l_shape();
+        let centroid = polygon.centroid().unwrap();
+        assert!((centroid.x - 5.0 / 3.0).abs() < 1e-9);
+        assert!((centroid.y - 5.0 / 3.0).abs() < 1e-9);
+        assert_eq!(polygon.perimeter(), 16.0);
+        assert_eq!(Polygon::<Point>::new().centroid(), None);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let bounding_box = l_shape().bounding_box().unwrap();
+        assert_eq!(bounding_box.min, PointF::new(0.0, 0.0));
+        assert_eq!(bounding_box.max, PointF::new(4.0, 4.0));
+    }
+
+    #[test]
+    fn test_closedness() {
+        let mut polygon = l_shape();
+        assert!(!polygon.is_closed());
+        polygon.close();
+        assert!(polygon.is_closed());
+        assert_eq!(polygon.len(), 7);
+        assert_eq!(polygon.signed_area(), 12.0);
+        polygon.open();
+        assert_eq!(polygon, l_shape());
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let polygon = l_shape();
+        assert!(polygon.contains_point(PointF::new(1.0, 3.0)));
+        assert!(polygon.contains_point(PointF::new(3.0, 1.0)));
+        assert!(!polygon.contains_point(PointF::new(3.0, 3.0)));
+        assert!(!polygon.contains_point(Point { x: 5, y: 1 }));
+    }
+}
@@ -0,0 +1,17 @@
+use super::{Point, PointF, Polygon};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Shape<P = Point> {
+    pub outer: Polygon<P>,
+    pub holes: Vec<Polygon<P>>,
+}
+
+impl From<Shape<Point>> for Shape<PointF> {
+    fn from(shape: Shape<Point>) -> Self {
+        Self {
+            outer: shape.outer.into(),
+            holes: shape.holes.into_iter().map(Polygon::from).collect(),
+        }
+    }
+}
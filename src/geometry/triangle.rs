@@ -1,4 +1,4 @@
-use crate::geometry::Point;
+use crate::geometry::{Point, PointF, Winding};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -25,3 +25,344 @@ impl PartialEq for Triangle {
         self_points == other_points
     }
 }
+
+impl From<[Point; 3]> for Triangle {
+    fn from([a, b, c]: [Point; 3]) -> Self {
+        Self(a, b, c)
+    }
+}
+
+impl From<Triangle> for [Point; 3] {
+    fn from(triangle: Triangle) -> Self {
+        [triangle.0, triangle.1, triangle.2]
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct TriangleF(pub PointF, pub PointF, pub PointF);
+
+impl IntoIterator for TriangleF {
+    type Item = PointF;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        vec![self.0, self.1, self.2].into_iter()
+    }
+}
+
+impl PartialEq for TriangleF {
+    fn eq(&self, other: &Self) -> bool {
+        let compare = |a: &PointF, b: &PointF| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y));
+        let mut self_points = vec![self.0, self.1, self.2];
+        let mut other_points = vec![other.0, other.1, other.2];
+        self_points.sort_by(compare);
+        other_points.sort_by(compare);
+        self_points == other_points
+    }
+}
+
+impl From<[PointF; 3]> for TriangleF {
+    fn from([a, b, c]: [PointF; 3]) -> Self {
+        Self(a, b, c)
+    }
+}
+
+impl From<TriangleF> for [PointF; 3] {
+    fn from(triangle: TriangleF) -> Self {
+        [triangle.0, triangle.1, triangle.2]
+    }
+}
+
+impl From<Triangle> for TriangleF {
+    fn from(triangle: Triangle) -> Self {
+        Self(triangle.0.into(), triangle.1.into(), triangle.2.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Circle {
+    pub center: PointF,
+    pub radius: f64,
+}
+
+/// Which points on the boundary of a triangle count as contained.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Containment {
+    /// Only points strictly inside the triangle.
+    Strict,
+    /// Points inside or anywhere on the edges, vertices included.
+    EdgeInclusive,
+    /// Points strictly inside, and the three vertices themselves.
+    VertexInclusive,
+}
+
+// Shared implementation of the methods of `Triangle` and `TriangleF`.
+#[derive(Clone, Copy)]
+struct Vertices([PointF; 3]);
+
+impl Vertices {
+    fn signed_area(&self) -> f64 {
+        let [a, b, c] = self.0;
+        (b - a).cross(c - a) / 2.0
+    }
+
+    fn centroid(&self) -> PointF {
+        let [a, b, c] = self.0;
+        (a + b + c) / 3.0
+    }
+
+    fn circumcircle(&self) -> Option<Circle> {
+        let [a, b, c] = self.0;
+        let (b, c) = (b - a, c - a);
+        let d = 2.0 * b.cross(c);
+        if d == 0.0 {
+            return None;
+        }
+
+        let offset = PointF::new(
+            (c.y * b.dot(b) - b.y * c.dot(c)) / d,
+            (b.x * c.dot(c) - c.x * b.dot(b)) / d,
+        );
+        Some(Circle {
+            center: a + offset,
+            radius: offset.length(),
+        })
+    }
+
+    fn barycentric(&self, point: PointF) -> Option<(f64, f64, f64)> {
+        let [a, b, c] = self.0;
+        let area = (b - a).cross(c - a);
+        if area == 0.0 {
+            return None;
+        }
+
+        let u = (b - point).cross(c - point) / area;
+        let v = (c - point).cross(a - point) / area;
+        Some((u, v, 1.0 - u - v))
+    }
+
+    fn contains(&self, point: PointF, containment: Containment) -> bool {
+        let [a, b, c] = self.0;
+        let is_vertex = self.0.contains(&point);
+        let sides = [
+            (b - a).cross(point - a),
+            (c - b).cross(point - b),
+            (a - c).cross(point - c),
+        ];
+        let is_inside =
+            sides.iter().all(|&side| side > 0.0) || sides.iter().all(|&side| side < 0.0);
+
+        match containment {
+            Containment::Strict => is_inside,
+            Containment::VertexInclusive => is_inside || is_vertex,
+            Containment::EdgeInclusive => {
+                let on_segment = |a: PointF, b: PointF| {
+                    (b - a).cross(point - a) == 0.0 && (point - a).dot(point - b) <= 0.0
+                };
+                !(sides.iter().any(|&side| side > 0.0) && sides.iter().any(|&side| side < 0.0))
+                    && (self.signed_area() != 0.0
+                        || on_segment(a, b)
+                        || on_segment(b, c)
+                        || on_segment(c, a))
+            }
+        }
+    }
+}
+
+impl Triangle {
+    fn vertices(&self) -> Vertices {
+        Vertices([self.0.into(), self.1.into(), self.2.into()])
+    }
+
+    /// Area of the triangle, positive when it winds clockwise on screen.
+    pub fn signed_area(&self) -> f64 {
+        self.vertices().signed_area()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    pub fn centroid(&self) -> PointF {
+        self.vertices().centroid()
+    }
+
+    /// Returns `None` when the vertices are collinear.
+    pub fn circumcircle(&self) -> Option<Circle> {
+        self.vertices().circumcircle()
+    }
+
+    /// Returns `None` when the vertices are collinear.
+    pub fn winding(&self) -> Option<Winding> {
+        Winding::of_signed_area(self.signed_area())
+    }
+
+    pub fn set_winding(&mut self, winding: Winding) {
+        if self.winding() == Some(winding.reversed()) {
+            std::mem::swap(&mut self.1, &mut self.2);
+        }
+    }
+
+    pub fn with_winding(mut self, winding: Winding) -> Self {
+        self.set_winding(winding);
+        self
+    }
+
+    /// Weights of the three vertices giving the point, or `None` when the vertices are collinear.
+    pub fn barycentric<Q: Into<PointF>>(&self, point: Q) -> Option<(f64, f64, f64)> {
+        self.vertices().barycentric(point.into())
+    }
+
+    pub fn contains<Q: Into<PointF>>(&self, point: Q, containment: Containment) -> bool {
+        self.vertices().contains(point.into(), containment)
+    }
+}
+
+impl TriangleF {
+    fn vertices(&self) -> Vertices {
+        Vertices([self.0, self.1, self.2])
+    }
+
+    /// Area of the triangle, positive when it winds clockwise on screen.
+    pub fn signed_area(&self) -> f64 {
+        self.vertices().signed_area()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    pub fn centroid(&self) -> PointF {
+        self.vertices().centroid()
+    }
+
+    /// Returns `None` when the vertices are collinear.
+    pub fn circumcircle(&self) -> Option<Circle> {
+        self.vertices().circumcircle()
+    }
+
+    /// Returns `None` when the vertices are collinear.
+    pub fn winding(&self) -> Option<Winding> {
+        Winding::of_signed_area(self.signed_area())
+    }
+
+    pub fn set_winding(&mut self, winding: Winding) {
+        if self.winding() == Some(winding.reversed()) {
+            std::mem::swap(&mut self.1, &mut self.2);
+        }
+    }
+
+    pub fn with_winding(mut self, winding: Winding) -> Self {
+        self.set_winding(winding);
+        self
+    }
+
+    /// Weights of the three vertices giving the point, or `None` when the vertices are collinear.
+    pub fn barycentric<Q: Into<PointF>>(&self, point: Q) -> Option<(f64, f64, f64)> {
+        self.vertices().barycentric(point.into())
+    }
+
+    pub fn contains<Q: Into<PointF>>(&self, point: Q, containment: Containment) -> bool {
+        self.vertices().contains(point.into(), containment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Triangle {
+        Triangle(
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 4 },
+            Point { x: 4, y: 0 },
+        )
+    }
+
+    #[test]
+    fn test_area_and_winding() {
+        let mut triangle = triangle();
+        assert_eq!(triangle.signed_area(), -8.0);
+        assert_eq!(triangle.winding(), Some(Winding::CounterClockwise));
+
+        triangle.set_winding(Winding::Clockwise);
+        assert_eq!(triangle.signed_area(), 8.0);
+        assert_eq!(triangle.area(), 8.0);
+        assert_eq!(triangle, self::triangle());
+    }
+
+    #[test]
+    fn test_centroid_and_circumcircle() {
+        let triangle = TriangleF(
+            PointF::new(0.0, 0.0),
+            PointF::new(4.0, 0.0),
+            PointF::new(0.0, 3.0),
+        );
+        assert_eq!(triangle.centroid(), PointF::new(4.0 / 3.0, 1.0));
+        assert_eq!(
+            triangle.circumcircle(),
+            Some(Circle {
+                center: PointF::new(2.0, 1.5),
+                radius: 2.5
+            })
+        );
+
+        let flat = TriangleF(
+            PointF::new(0.0, 0.0),
+            PointF::new(1.0, 1.0),
+            PointF::new(2.0, 2.0),
+        );
+        assert_eq!(flat.circumcircle(), None);
+        assert_eq!(flat.winding(), None);
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let triangle = triangle();
+        assert_eq!(triangle.barycentric(triangle.1), Some((0.0, 1.0, 0.0)));
+        let (u, v, w) = triangle.barycentric(triangle.centroid()).unwrap();
+        assert!((u - 1.0 / 3.0).abs() < 1e-9);
+        assert!((v - 1.0 / 3.0).abs() < 1e-9);
+        assert!((w - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_contains() {
+        let triangle = triangle();
+        let inside = Point { x: 2, y: 1 };
+        let on_edge = Point { x: 2, y: 0 };
+        let vertex = Point { x: 4, y: 0 };
+        let outside = Point { x: 2, y: 3 };
+
+        assert!(triangle.contains(inside, Containment::Strict));
+        assert!(!triangle.contains(on_edge, Containment::Strict));
+        assert!(!triangle.contains(vertex, Containment::Strict));
+
+        assert!(triangle.contains(on_edge, Containment::EdgeInclusive));
+        assert!(triangle.contains(vertex, Containment::EdgeInclusive));
+
+        assert!(!triangle.contains(on_edge, Containment::VertexInclusive));
+        assert!(triangle.contains(vertex, Containment::VertexInclusive));
+
+        for containment in [
+            Containment::Strict,
+            Containment::EdgeInclusive,
+            Containment::VertexInclusive,
+        ] {
+            assert!(!triangle.contains(outside, containment));
+        }
+    }
+
+    #[test]
+    fn test_contains_degenerate() {
+        let segment = Triangle(
+            Point { x: 0, y: 0 },
+            Point { x: 2, y: 2 },
+            Point { x: 4, y: 4 },
+        );
+        assert!(segment.contains(Point { x: 1, y: 1 }, Containment::EdgeInclusive));
+        assert!(!segment.contains(Point { x: 5, y: 5 }, Containment::EdgeInclusive));
+        assert!(!segment.contains(Point { x: 1, y: 1 }, Containment::Strict));
+    }
+}
@@ -0,0 +1,49 @@
+use super::{Point, PointF, Triangle, TriangleF};
+
+/// A point type the geometry algorithms of this crate can work on, either the integer [`Point`]
+/// or the floating-point [`PointF`]. Computations are carried out in `f64`.
+pub trait Vertex: Copy + PartialEq + Into<PointF> {
+    type Triangle: Face<Vertex = Self> + From<[Self; 3]> + Into<[Self; 3]>;
+
+    /// Converts back from a computed point, rounding to the nearest pixel for [`Point`].
+    fn from_point_f(point: PointF) -> Self;
+}
+
+/// A triangle made of one of the [`Vertex`] types.
+pub trait Face: Copy {
+    type Vertex: Vertex;
+
+    fn vertices(self) -> [Self::Vertex; 3];
+}
+
+impl Vertex for Point {
+    type Triangle = Triangle;
+
+    fn from_point_f(point: PointF) -> Self {
+        Point::new(point.x.round() as u32, point.y.round() as u32)
+    }
+}
+
+impl Vertex for PointF {
+    type Triangle = TriangleF;
+
+    fn from_point_f(point: PointF) -> Self {
+        point
+    }
+}
+
+impl Face for Triangle {
+    type Vertex = Point;
+
+    fn vertices(self) -> [Point; 3] {
+        [self.0, self.1, self.2]
+    }
+}
+
+impl Face for TriangleF {
+    type Vertex = PointF;
+
+    fn vertices(self) -> [PointF; 3] {
+        [self.0, self.1, self.2]
+    }
+}
@@ -1,197 +1,713 @@
 use crate::geometry::*;
+use crate::Error;
 use image;
 use image::GenericImageView;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
 pub type Grid<T> = Vec<Vec<T>>;
 
+/// Largest width or height accepted by [`ImagePolygon`], so that differences and cross products of
+/// coordinates always fit in an `i64`.
+pub const MAX_IMAGE_DIMENSION: u32 = i32::MAX as u32;
+
+pub type PixelPredicate = dyn Fn(image::Rgba<u8>) -> bool + Send + Sync;
+
+/// Decides which pixels of an image are solid.
+///
+/// By default a pixel is solid as soon as its alpha channel reaches `alpha_threshold`. A custom
+/// predicate replaces the alpha test entirely, which allows color keys or luminance masks for
+/// images without an alpha channel.
+#[derive(Clone)]
+pub struct MaskOptions {
+    alpha_threshold: u8,
+    predicate: Option<Arc<PixelPredicate>>,
+}
+
+impl MaskOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alpha_threshold(mut self, alpha_threshold: u8) -> Self {
+        self.alpha_threshold = alpha_threshold;
+        self
+    }
+
+    pub fn predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(image::Rgba<u8>) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Treats every pixel of the given color as transparent, whatever its alpha channel.
+    pub fn color_key(self, key: image::Rgb<u8>) -> Self {
+        self.predicate(move |pixel| pixel.0[..3] != key.0)
+    }
+
+    pub fn is_solid(&self, pixel: image::Rgba<u8>) -> bool {
+        match &self.predicate {
+            Some(predicate) => predicate(pixel),
+            None => pixel.0[3] >= self.alpha_threshold,
+        }
+    }
+}
+
+impl Default for MaskOptions {
+    fn default() -> Self {
+        Self {
+            alpha_threshold: 1,
+            predicate: None,
+        }
+    }
+}
+
+impl fmt::Debug for MaskOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaskOptions")
+            .field("alpha_threshold", &self.alpha_threshold)
+            .field("predicate", &self.predicate.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
 pub struct ImagePolygon {
     image: image::DynamicImage,
-    size: (u16, u16),
+    size: (u32, u32),
+    options: MaskOptions,
 }
 
 impl ImagePolygon {
+    /// Panics when the image is larger than [`MAX_IMAGE_DIMENSION`].
+    #[deprecated(note = "use `ImagePolygon::try_new`, which returns an error for oversized images")]
     pub fn new(image: image::DynamicImage) -> Self {
-        let dimensions = image.dimensions();
-        let size = (dimensions.0 as u16, dimensions.1 as u16);
-        Self { image, size }
+        Self::try_new(image).expect("Image is too large")
+    }
+
+    /// Fails with [`Error::ImageTooLarge`] when a side is larger than [`MAX_IMAGE_DIMENSION`].
+    pub fn try_new(image: image::DynamicImage) -> Result<Self, Error> {
+        let (width, height) = image.dimensions();
+        if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+            return Err(Error::ImageTooLarge { width, height });
+        }
+        Ok(Self {
+            image,
+            size: (width, height),
+            options: MaskOptions::default(),
+        })
+    }
+
+    pub fn with_options(mut self, options: MaskOptions) -> Self {
+        self.options = options;
+        self
     }
 
     fn get_opaque_points_grid(&self) -> Grid<bool> {
-        let rgba = &self.image.to_rgba16();
+        let rgba = &self.image.to_rgba8();
         let mut opaque_points: Grid<bool> =
             vec![vec![false; self.size.0 as usize]; self.size.1 as usize];
         for y in 0..rgba.height() {
             for x in 0..rgba.width() {
                 let pixel = rgba.get_pixel(x, y);
-                let alpha_channel = pixel.0[3];
-                opaque_points[y as usize][x as usize] = alpha_channel > 0;
+                opaque_points[y as usize][x as usize] = self.options.is_solid(*pixel);
             }
         }
         opaque_points
     }
 
-    fn get_edges_points(&self) -> Vec<Point> {
+    pub fn to_polygon(&self) -> Result<(Polygon, Vec<Polygon>), Error> {
+        let shape = self.to_shapes()?.swap_remove(0);
+        Ok((shape.outer, shape.holes))
+    }
+
+    /// Traces every disconnected opaque island of the image, in the order their top-left-most pixel
+    /// appears. Islands lying inside another island's hole are returned as separate shapes, and
+    /// islands whose outline has fewer than 3 points are skipped.
+    pub fn to_shapes(&self) -> Result<Vec<Shape>, Error> {
         let opaque_points = self.get_opaque_points_grid();
-        let mut edge_points = Vec::new();
-
-        for (i, &is_opaque) in opaque_points.iter().flatten().enumerate() {
-            if is_opaque {
-                let x = i % self.size.0 as usize;
-                let y = i / self.size.0 as usize;
-
-                let left = opaque_points
-                    .get(y)
-                    .and_then(|row| row.get(x.wrapping_sub(1)));
-                let right = opaque_points.get(y).and_then(|row| row.get(x + 1));
-                let up = opaque_points
-                    .get(y.wrapping_sub(1))
-                    .and_then(|row| row.get(x));
-                let down = opaque_points.get(y + 1).and_then(|row| row.get(x));
-
-                if left != Some(&true)
-                    || right != Some(&true)
-                    || up != Some(&true)
-                    || down != Some(&true)
-                    || x == 0
-                    || x == self.size.0 as usize
-                    || y == 0
-                    || y == self.size.1 as usize
-                {
-                    edge_points.push(Point {
-                        x: x as u16,
-                        y: y as u16,
-                    });
+        let (islands, islands_count) = label_components(&opaque_points, true, true);
+        if islands_count == 0 {
+            return Err(Error::EmptyMask);
+        }
+        let (gaps, gaps_count) = label_components(&opaque_points, false, false);
+
+        let (width, height) = (self.size.0 as usize, self.size.1 as usize);
+
+        // Transparent areas touching the border of the image are part of the outside, the others
+        // are holes of the island that surrounds them.
+        let mut is_outside = vec![false; gaps_count];
+        for (y, row) in gaps.iter().enumerate() {
+            for (x, gap) in row.iter().enumerate() {
+                if let Some(gap) = gap {
+                    if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                        is_outside[*gap] = true;
+                    }
                 }
             }
         }
 
-        edge_points
-    }
-
-    fn get_first_point_of_main_polygon(&self) -> Point {
-        let grid = self.get_opaque_points_grid();
-        for (y, row) in grid.iter().enumerate() {
-            for (x, &is_opaque) in row.iter().enumerate() {
-                if is_opaque {
-                    return Point {
-                        x: x as u16,
-                        y: y as u16,
-                    };
+        // The first pixel of an island has a transparent pixel on its left, and the pixel above the
+        // first pixel of a hole belongs to the island surrounding it.
+        let mut island_starts: Vec<Option<(usize, usize)>> = vec![None; islands_count];
+        let mut hole_starts: Vec<Option<(usize, usize)>> = vec![None; gaps_count];
+        for (y, row) in islands.iter().enumerate() {
+            for (x, &island) in row.iter().enumerate() {
+                if let Some(island) = island {
+                    island_starts[island].get_or_insert((x, y));
+                } else if let Some(gap) = gaps[y][x].filter(|&gap| !is_outside[gap]) {
+                    hole_starts[gap].get_or_insert((x, y - 1));
                 }
             }
         }
-        panic!("No opaque points found");
+
+        let mut shapes: Vec<Shape> = island_starts
+            .into_iter()
+            .flatten()
+            .map(|start| Shape {
+                outer: trace_border(&opaque_points, start, WEST),
+                holes: Vec::new(),
+            })
+            .collect();
+        for (x, y) in hole_starts.into_iter().flatten() {
+            if let Some(island) = islands[y][x] {
+                shapes[island]
+                    .holes
+                    .push(trace_border(&opaque_points, (x, y), SOUTH));
+            }
+        }
+
+        let first_len = shapes[0].outer.len();
+        shapes.retain(|shape| shape.outer.len() >= 3);
+        if shapes.is_empty() {
+            return Err(Error::DegenerateContour { len: first_len });
+        }
+
+        Ok(shapes)
     }
 
-    pub fn to_polygon(&self) -> (Polygon, Vec<Polygon>) {
-        let mut holes: Vec<Polygon> = Vec::new();
-        let edge_points = self.get_edges_points();
+    /// Extracts the contours with marching squares, interpolating along the iso-line of the alpha
+    /// channel at the alpha threshold instead of following pixel centers. Pixel `(x, y)` covers the
+    /// square from `(x, y)` to `(x + 1, y + 1)`, so a hard edge between an opaque and a transparent
+    /// pixel lies on their shared side when the threshold is half way. When a custom predicate is
+    /// set, the contours follow the sides of the solid pixels.
+    ///
+    /// Outer contours wind clockwise on screen (y pointing down) and holes counter-clockwise.
+    pub fn to_subpixel_shapes(&self) -> Result<Vec<Shape<PointF>>, Error> {
+        let rgba = self.image.to_rgba8();
+        let (width, height) = (self.size.0 as usize, self.size.1 as usize);
+        let (values, level): (Grid<f64>, f64) = match &self.options.predicate {
+            Some(predicate) => (
+                rgba.rows()
+                    .map(|row| {
+                        row.map(|&pixel| if predicate(pixel) { 255.0 } else { 0.0 })
+                            .collect()
+                    })
+                    .collect(),
+                127.5,
+            ),
+            None => (
+                rgba.rows()
+                    .map(|row| row.map(|pixel| pixel.0[3] as f64).collect())
+                    .collect(),
+                self.options.alpha_threshold as f64,
+            ),
+        };
 
-        let current_point = self.get_first_point_of_main_polygon();
+        // The samples are the pixel centers, surrounded by a ring of transparent samples so that
+        // every contour is closed. The ring stays below the level even at a threshold of 0, where
+        // every pixel is solid.
+        let padding = (level - 1.0).min(0.0);
+        let sample = |i: usize, j: usize| -> f64 {
+            if i == 0 || j == 0 || i > width || j > height {
+                padding
+            } else {
+                values[j - 1][i - 1]
+            }
+        };
+        let horizontal_edges = (width + 1) * (height + 2);
+        let edge_point = |edge: usize| -> PointF {
+            let ((i0, j0), (i1, j1)) = if edge < horizontal_edges {
+                let (i, j) = (edge % (width + 1), edge / (width + 1));
+                ((i, j), (i + 1, j))
+            } else {
+                let edge = edge - horizontal_edges;
+                let (i, j) = (edge % (width + 2), edge / (width + 2));
+                ((i, j), (i, j + 1))
+            };
+            let (v0, v1) = (sample(i0, j0), sample(i1, j1));
+            let t = (level - v0) / (v1 - v0);
+            PointF {
+                x: i0 as f64 - 0.5 + t * (i1 as f64 - i0 as f64),
+                y: j0 as f64 - 0.5 + t * (j1 as f64 - j0 as f64),
+            }
+        };
 
-        let polygon = get_polygon_from_point(current_point, edge_points.clone());
+        // Every cell links the edges where its contour enters and leaves, keeping the solid side
+        // on the right of the contour.
+        let mut starts: Vec<usize> = Vec::new();
+        let mut next: HashMap<usize, usize> = HashMap::new();
+        for j in 0..=height {
+            for i in 0..=width {
+                let corners = [
+                    sample(i, j),
+                    sample(i + 1, j),
+                    sample(i + 1, j + 1),
+                    sample(i, j + 1),
+                ];
+                let solid = corners.map(|value| value >= level);
+                // Sides of the cell, clockwise from the top one.
+                let sides = [
+                    j * (width + 1) + i,
+                    horizontal_edges + j * (width + 2) + i + 1,
+                    (j + 1) * (width + 1) + i,
+                    horizontal_edges + j * (width + 2) + i,
+                ];
+                let leaving: Vec<usize> = (0..4)
+                    .filter(|&k| solid[k] && !solid[(k + 1) % 4])
+                    .collect();
+                let entering: Vec<usize> = (0..4)
+                    .filter(|&k| !solid[k] && solid[(k + 1) % 4])
+                    .collect();
 
-        // If there's some points that are in the edge_points list but not in the polygon list, it means that there's holes
-        let mut remaining_points = edge_points
-            .iter()
-            .filter(|point| !polygon.contains(point))
-            .cloned()
-            .collect::<Vec<_>>();
-
-        if edge_points.len() > polygon.len() {
-            loop {
-                if remaining_points.is_empty() {
-                    break;
+                let links: Vec<(usize, usize)> = match (leaving.as_slice(), entering.as_slice()) {
+                    (&[from], &[to]) => vec![(from, to)],
+                    (&[first, second], _) => {
+                        let center = corners.iter().sum::<f64>() / 4.0;
+                        if center >= level {
+                            vec![(first, (first + 1) % 4), (second, (second + 1) % 4)]
+                        } else {
+                            vec![(first, (first + 3) % 4), (second, (second + 3) % 4)]
+                        }
+                    }
+                    _ => vec![],
+                };
+                for (from, to) in links {
+                    starts.push(sides[from]);
+                    next.insert(sides[from], sides[to]);
                 }
+            }
+        }
 
-                let hole = get_polygon_from_point(remaining_points[0], remaining_points.clone());
+        let mut rings: Vec<Polygon<PointF>> = Vec::new();
+        for start in starts {
+            let Some(mut edge) = next.remove(&start) else {
+                continue;
+            };
+            let mut ring: Polygon<PointF> = Polygon::from(vec![edge_point(start)]);
+            while edge != start {
+                let point = edge_point(edge);
+                if ring.last() != Some(&point) {
+                    ring.push(point);
+                }
+                edge = next.remove(&edge).unwrap();
+            }
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            if ring.len() >= 3 {
+                rings.push(ring);
+            }
+        }
 
-                remaining_points = remaining_points
-                    .iter()
-                    .filter(|point| !hole.contains(point))
-                    .cloned()
-                    .collect::<Vec<_>>();
+        let (outers, holes): (Vec<_>, Vec<_>) = rings
+            .into_iter()
+            .map(|ring| (ring.signed_area(), ring))
+            .partition(|(area, _)| *area > 0.0);
+        if outers.is_empty() {
+            return Err(Error::EmptyMask);
+        }
 
-                holes.push(hole);
+        let mut shapes: Vec<Shape<PointF>> = outers
+            .iter()
+            .map(|(_, outer)| Shape {
+                outer: outer.clone(),
+                holes: Vec::new(),
+            })
+            .collect();
+        // A hole belongs to the smallest outer contour around it.
+        for (_, hole) in holes {
+            let owner = outers
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, outer))| outer.contains_point(hole[0]))
+                .min_by(|(_, (a, _)), (_, (b, _))| a.total_cmp(b))
+                .map(|(index, _)| index);
+            if let Some(owner) = owner {
+                shapes[owner].holes.push(hole);
             }
         }
 
-        (polygon, holes)
+        Ok(shapes)
     }
 }
 
-fn get_polygon_from_point(point: Point, all_points: Vec<Point>) -> Polygon {
-    let mut polygon: Polygon = Vec::new();
-    let mut current_point = point;
-    loop {
-        polygon.push(current_point);
-        let right_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.right().as_ref());
-        let right_up_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.right_up().as_ref());
-        let right_down_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.right_down().as_ref());
-        let up_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.up().as_ref());
-        let down_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.down().as_ref());
-        let left_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.left().as_ref());
-        let left_up_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.left_up().as_ref());
-        let left_down_point = all_points
-            .iter()
-            .find(|&point| Some(point) == current_point.left_down().as_ref());
+/// Labels the connected components of the cells equal to `value`, using 8-connectivity when
+/// `diagonal` is set and 4-connectivity otherwise. Components are numbered in raster order.
+fn label_components(
+    grid: &Grid<bool>,
+    value: bool,
+    diagonal: bool,
+) -> (Grid<Option<usize>>, usize) {
+    let mut labels: Grid<Option<usize>> = grid.iter().map(|row| vec![None; row.len()]).collect();
+    let mut count = 0;
+    let mut stack: Vec<(usize, usize)> = Vec::new();
 
-        match (
-            right_point,
-            right_up_point,
-            right_down_point,
-            up_point,
-            down_point,
-            left_point,
-            left_up_point,
-            left_down_point,
-        ) {
-            (Some(point), _, _, _, _, _, _, _) if !polygon.contains(point) => {
-                current_point = *point;
-            }
-            (_, Some(point), _, _, _, _, _, _) if !polygon.contains(point) => {
-                current_point = *point;
+    for y in 0..grid.len() {
+        for x in 0..grid[y].len() {
+            if grid[y][x] != value || labels[y][x].is_some() {
+                continue;
             }
-            (_, _, Some(point), _, _, _, _, _) if !polygon.contains(point) => {
-                current_point = *point;
-            }
-            (_, _, _, Some(point), _, _, _, _) if !polygon.contains(point) => {
-                current_point = *point;
-            }
-            (_, _, _, _, Some(point), _, _, _) if !polygon.contains(point) => {
-                current_point = *point;
+
+            labels[y][x] = Some(count);
+            stack.push((x, y));
+            while let Some((x, y)) = stack.pop() {
+                for dy in -1isize..=1 {
+                    for dx in -1isize..=1 {
+                        if (dx == 0 && dy == 0) || (!diagonal && dx != 0 && dy != 0) {
+                            continue;
+                        }
+                        let (Some(nx), Some(ny)) =
+                            (x.checked_add_signed(dx), y.checked_add_signed(dy))
+                        else {
+                            continue;
+                        };
+                        if grid.get(ny).and_then(|row| row.get(nx)) == Some(&value)
+                            && labels[ny][nx].is_none()
+                        {
+                            labels[ny][nx] = Some(count);
+                            stack.push((nx, ny));
+                        }
+                    }
+                }
             }
-            (_, _, _, _, _, Some(point), _, _) if !polygon.contains(point) => {
-                current_point = *point;
+            count += 1;
+        }
+    }
+
+    (labels, count)
+}
+
+// Neighbor directions, clockwise as seen on screen (y pointing down).
+const DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+const SOUTH: usize = 2;
+const WEST: usize = 4;
+
+/// Follows the border of the 8-connected component containing `start` (Suzuki–Abe border
+/// following), keeping the transparent area lying in the `background` direction of `start` on the
+/// same side. Every step only looks at the 8 neighbors of the current pixel, so the whole border is
+/// traced in time linear in its length.
+fn trace_border(grid: &Grid<bool>, start: (usize, usize), background: usize) -> Polygon {
+    let neighbor = |(x, y): (usize, usize), direction: usize| -> Option<(usize, usize)> {
+        let (dx, dy) = DIRECTIONS[direction];
+        let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        grid.get(y)
+            .and_then(|row| row.get(x))
+            .filter(|&&is_opaque| is_opaque)
+            .map(|_| (x, y))
+    };
+    let direction = |from: (usize, usize), to: (usize, usize)| -> usize {
+        let delta = (
+            to.0 as isize - from.0 as isize,
+            to.1 as isize - from.1 as isize,
+        );
+        DIRECTIONS.iter().position(|&d| d == delta).unwrap()
+    };
+    let to_point = |(x, y): (usize, usize)| Point {
+        x: x as u32,
+        y: y as u32,
+    };
+
+    let mut polygon: Polygon = Polygon::from(vec![to_point(start)]);
+
+    let Some(first) = (0..8).find_map(|i| neighbor(start, (background + i) % 8)) else {
+        return polygon;
+    };
+
+    let mut previous = first;
+    let mut current = start;
+    loop {
+        let from = direction(current, previous);
+        let next = (1..=8)
+            .find_map(|i| neighbor(current, (from + 8 - i) % 8))
+            .unwrap();
+        if next == start && current == first {
+            break;
+        }
+        previous = current;
+        current = next;
+        polygon.push(to_point(current));
+    }
+
+    polygon
+}
+
+impl TryFrom<&[u8]> for ImagePolygon {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_new(image::load_from_memory(data)?)
+    }
+}
+
+impl TryFrom<Vec<u8>> for ImagePolygon {
+    type Error = Error;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from(data.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_image() -> image::DynamicImage {
+        let mut image = image::RgbaImage::new(8, 8);
+        for y in 2..6 {
+            for x in 2..6 {
+                image.put_pixel(x, y, image::Rgba([255, 0, 0, 255]));
             }
-            (_, _, _, _, _, _, Some(point), _) if !polygon.contains(point) => {
-                current_point = *point;
+        }
+        image::DynamicImage::ImageRgba8(image)
+    }
+
+    #[test]
+    fn test_to_polygon() {
+        let (polygon, holes) = ImagePolygon::try_new(square_image())
+            .unwrap()
+            .to_polygon()
+            .unwrap();
+        assert_eq!(polygon.len(), 12);
+        assert!(holes.is_empty());
+    }
+
+    fn image_from_rows(rows: &[&str]) -> image::DynamicImage {
+        let mut image = image::RgbaImage::new(rows[0].len() as u32, rows.len() as u32);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, pixel) in row.chars().enumerate() {
+                if pixel == '#' {
+                    image.put_pixel(x as u32, y as u32, image::Rgba([0, 0, 0, 255]));
+                }
             }
-            (_, _, _, _, _, _, _, Some(point)) if !polygon.contains(point) => {
-                current_point = *point;
+        }
+        image::DynamicImage::ImageRgba8(image)
+    }
+
+    #[test]
+    fn test_to_shapes_disconnected_islands() {
+        let image = image_from_rows(&[
+            "..........",
+            ".###...##.",
+            ".###...##.",
+            ".###...##.",
+            "..........",
+        ]);
+        let shapes = ImagePolygon::try_new(image).unwrap().to_shapes().unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert!(shapes.iter().all(|shape| shape.holes.is_empty()));
+        assert_eq!(shapes[0].outer.len(), 8);
+        assert_eq!(shapes[1].outer.len(), 6);
+    }
+
+    #[test]
+    fn test_to_shapes_island_inside_hole() {
+        let image = image_from_rows(&[
+            "#########",
+            "#.......#",
+            "#.#####.#",
+            "#.#...#.#",
+            "#.#.#.#.#",
+            "#.#...#.#",
+            "#.#####.#",
+            "#.......#",
+            "#########",
+        ]);
+        let shapes = ImagePolygon::try_new(image).unwrap().to_shapes().unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shapes[0].outer.len(), 32);
+        assert_eq!(shapes[0].holes.len(), 1);
+        assert_eq!(shapes[0].holes[0].len(), 28);
+        assert_eq!(shapes[1].outer.len(), 16);
+        assert_eq!(shapes[1].holes.len(), 1);
+        assert_eq!(shapes[1].holes[0].len(), 12);
+    }
+
+    #[test]
+    fn test_alpha_threshold() {
+        let mut image = square_image().to_rgba8();
+        for x in 1..7 {
+            image.put_pixel(x, 1, image::Rgba([255, 0, 0, 40]));
+        }
+        let image = image::DynamicImage::ImageRgba8(image);
+
+        let (polygon, _) = ImagePolygon::try_new(image.clone())
+            .unwrap()
+            .to_polygon()
+            .unwrap();
+        assert_eq!(polygon[0], Point { x: 1, y: 1 });
+
+        let (polygon, _) = ImagePolygon::try_new(image)
+            .unwrap()
+            .with_options(MaskOptions::new().alpha_threshold(128))
+            .to_polygon()
+            .unwrap();
+        assert_eq!(polygon[0], Point { x: 2, y: 2 });
+        assert_eq!(polygon.len(), 12);
+    }
+
+    #[test]
+    fn test_color_key() {
+        let mut image = image::RgbImage::from_pixel(8, 8, image::Rgb([255, 0, 255]));
+        for y in 2..6 {
+            for x in 2..6 {
+                image.put_pixel(x, y, image::Rgb([0, 128, 0]));
             }
-            _ => break,
         }
+        let image = image::DynamicImage::ImageRgb8(image);
+
+        let (polygon, holes) = ImagePolygon::try_new(image)
+            .unwrap()
+            .with_options(MaskOptions::new().color_key(image::Rgb([255, 0, 255])))
+            .to_polygon()
+            .unwrap();
+        assert_eq!(polygon[0], Point { x: 2, y: 2 });
+        assert_eq!(polygon.len(), 12);
+        assert!(holes.is_empty());
     }
 
-    polygon
-}
-impl From<Vec<u8>> for ImagePolygon {
-    fn from(data: Vec<u8>) -> Self {
-        Self::new(image::load_from_memory(&data).unwrap())
+    #[test]
+    fn test_to_subpixel_shapes() {
+        let shapes = ImagePolygon::try_new(square_image())
+            .unwrap()
+            .with_options(MaskOptions::new().predicate(|pixel| pixel.0[3] > 0))
+            .to_subpixel_shapes()
+            .unwrap();
+        assert_eq!(shapes.len(), 1);
+        assert!(shapes[0].holes.is_empty());
+
+        let outer = &shapes[0].outer;
+        assert_eq!(outer.len(), 16);
+        assert_eq!(outer.signed_area(), 15.5);
+        assert!(outer
+            .iter()
+            .all(|point| (2.0..=6.0).contains(&point.x) && (2.0..=6.0).contains(&point.y)));
+    }
+
+    #[test]
+    fn test_to_subpixel_shapes_interpolation() {
+        let mut image = image::RgbaImage::new(3, 3);
+        image.put_pixel(1, 1, image::Rgba([0, 0, 0, 200]));
+        let image = image::DynamicImage::ImageRgba8(image);
+
+        let shapes = ImagePolygon::try_new(image)
+            .unwrap()
+            .with_options(MaskOptions::new().alpha_threshold(100))
+            .to_subpixel_shapes()
+            .unwrap();
+        assert_eq!(
+            shapes[0].outer,
+            vec![
+                PointF { x: 1.0, y: 1.5 },
+                PointF { x: 1.5, y: 1.0 },
+                PointF { x: 2.0, y: 1.5 },
+                PointF { x: 1.5, y: 2.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zero_alpha_threshold() {
+        let image = ImagePolygon::try_new(square_image())
+            .unwrap()
+            .with_options(MaskOptions::new().alpha_threshold(0));
+
+        let shapes = image.to_shapes().unwrap();
+        let subpixel_shapes = image.to_subpixel_shapes().unwrap();
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(subpixel_shapes.len(), 1);
+        assert!(shapes[0].holes.is_empty() && subpixel_shapes[0].holes.is_empty());
+        // Both go through the centers of the border pixels, half a pixel apart
+        assert_eq!(shapes[0].outer.area(), 49.0);
+        assert_eq!(subpixel_shapes[0].outer.signed_area(), 49.0);
+        let bounds = subpixel_shapes[0].outer.bounding_box().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (PointF::new(0.5, 0.5), PointF::new(7.5, 7.5))
+        );
+    }
+
+    #[test]
+    fn test_to_subpixel_shapes_island_inside_hole() {
+        let image = image_from_rows(&[
+            "#######", "#.....#", "#.....#", "#..#..#", "#.....#", "#.....#", "#######",
+        ]);
+        let shapes = ImagePolygon::try_new(image)
+            .unwrap()
+            .with_options(MaskOptions::new().alpha_threshold(128))
+            .to_subpixel_shapes()
+            .unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shapes[0].holes.len(), 1);
+        assert_eq!(shapes[0].outer.winding(), Some(Winding::Clockwise));
+        assert_eq!(
+            shapes[0].holes[0].winding(),
+            Some(Winding::CounterClockwise)
+        );
+        assert!(shapes[1].holes.is_empty());
+    }
+
+    #[test]
+    fn test_to_polygon_transparent_image() {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::new(8, 8));
+        assert!(matches!(
+            ImagePolygon::try_new(image).unwrap().to_polygon(),
+            Err(Error::EmptyMask)
+        ));
+    }
+
+    #[test]
+    fn test_to_polygon_single_pixel() {
+        let mut image = image::RgbaImage::new(8, 8);
+        image.put_pixel(3, 3, image::Rgba([0, 0, 0, 255]));
+        let image = image::DynamicImage::ImageRgba8(image);
+        assert!(matches!(
+            ImagePolygon::try_new(image).unwrap().to_polygon(),
+            Err(Error::DegenerateContour { len: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_try_from_invalid_bytes() {
+        assert!(matches!(
+            ImagePolygon::try_from(&b"not an image"[..]),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_polygon_wide_image() {
+        let mut image = image::RgbaImage::new(70_000, 4);
+        for y in 1..3 {
+            for x in 69_990..69_993 {
+                image.put_pixel(x, y, image::Rgba([0, 0, 0, 255]));
+            }
+        }
+        let image = image::DynamicImage::ImageRgba8(image);
+
+        let (polygon, _) = ImagePolygon::try_new(image).unwrap().to_polygon().unwrap();
+        assert_eq!(polygon[0], Point { x: 69_990, y: 1 });
+        assert!(polygon.iter().all(|point| point.x >= 69_990));
     }
 }
@@ -1,16 +1,33 @@
 #![cfg_attr(feature = "_nightly-toolchain", feature(test))]
 #![cfg_attr(target_arch = "wasm32", recursion_limit = "512")]
 
+pub mod collision;
+pub mod decomposition;
+mod error;
+pub mod export;
 pub mod geometry;
 pub mod image;
+pub mod pipeline;
 pub mod rdp;
+pub mod simplify;
 pub mod triangulation;
+pub mod visvalingam;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use error::Error;
 
 #[cfg(test)]
 mod benches {
     extern crate test;
 
-    use super::{image::ImagePolygon, rdp::rdp, triangulation::triangulate};
+    use super::{
+        image::ImagePolygon,
+        pipeline::{Pipeline, PipelineOptions},
+        rdp::rdp,
+        triangulation::{triangulate, TriangulationOptions},
+    };
+    use crate::collision;
     use crate::geometry::Triangle;
     use test::Bencher;
 
@@ -21,13 +38,11 @@ mod benches {
             return;
         }
 
+        let pipeline = Pipeline::new(PipelineOptions::default());
         b.iter(|| {
             let image = image::open(std::env::var("CAZAN_IMAGE_PATH").unwrap())
                 .expect("Error opening image");
-            let (polygon, holes) = ImagePolygon::new(image).to_polygon();
-            let rdp_polygon = rdp(&polygon, 1.0);
-            let rdp_holes = &holes.iter().map(|hole| rdp(hole, 1.0)).collect();
-            triangulate(&rdp_polygon, Some(rdp_holes)).expect("Error triangulating");
+            pipeline.run(image).expect("Error building hitbox");
         });
     }
 
@@ -44,10 +59,17 @@ mod benches {
 
             let image = image::open(std::env::var("CAZAN_IMAGE_PATH").unwrap())
                 .expect("Error opening image");
-            let (polygon, holes) = ImagePolygon::new(image).to_polygon();
+            let (polygon, holes) = ImagePolygon::try_new(image)
+                .and_then(|image| image.to_polygon())
+                .expect("Error interpreting image");
             let rdp_polygon = rdp(&polygon, 1.0);
             let rdp_holes = &holes.iter().map(|hole| rdp(hole, 1.0)).collect();
-            t1 = triangulate(&rdp_polygon, Some(rdp_holes)).expect("Error triangulating");
+            t1 = triangulate(
+                &rdp_polygon,
+                Some(rdp_holes),
+                &TriangulationOptions::default(),
+            )
+            .expect("Error triangulating");
             t2 = t1.clone();
         }
 
@@ -71,13 +93,7 @@ mod benches {
         b.iter(|| {
             for triangle in t1.iter() {
                 for triangle2 in t2.iter() {
-                    for point in triangle.into_iter() {
-                        for point2 in triangle2.into_iter() {
-                            let x = point.x as i32 - point2.x as i32;
-                            let y = point.y as i32 - point2.y as i32;
-                            let _ = (x, y);
-                        }
-                    }
+                    test::black_box(collision::minkowski_difference(triangle, triangle2));
                 }
             }
         });
@@ -0,0 +1,346 @@
+//! The whole image to hitbox pipeline in one call: extracting the shapes of an image,
+//! simplifying them, then triangulating or decomposing them, all driven by a serializable
+//! [`PipelineOptions`].
+
+use image::GenericImageView;
+use serde::{Deserialize, Serialize};
+
+use crate::decomposition::{hertel_mehlhorn, DecompositionOptions};
+use crate::geometry::*;
+use crate::image::{ImagePolygon, MaskOptions};
+use crate::simplify::{self, simplify_ring, Target};
+use crate::triangulation::{triangulate, TriangulationOptions};
+use crate::Error;
+
+/// What the pipeline produces from the shapes of the image.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Output {
+    #[default]
+    Triangles,
+    /// See [`hertel_mehlhorn`].
+    ConvexPieces,
+    /// The simplified outer rings and holes.
+    Polygons,
+}
+
+/// Origin and orientation of the coordinates of the result.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Origin {
+    /// The top-left corner of the image, y pointing down.
+    #[default]
+    TopLeft,
+    /// The center of the image, y pointing down.
+    Center,
+    /// The bottom-left corner of the image, y pointing up. Rings and triangles are reversed so
+    /// that their signed area keeps its sign.
+    BottomLeft,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineOptions {
+    /// Smallest alpha value of a solid pixel.
+    pub alpha_threshold: u8,
+    /// Follows the edges of the pixels instead of their centers, see
+    /// [`ImagePolygon::to_subpixel_shapes`].
+    pub subpixel: bool,
+    /// RDP keeps the topology of each shape, see [`simplify::simplify`]. Visvalingam–Whyatt
+    /// simplifies each ring on its own.
+    pub algorithm: simplify::Algorithm,
+    /// Tolerance of the simplification: a distance for RDP, an area for Visvalingam–Whyatt.
+    pub epsilon: f64,
+    pub output: Output,
+    pub origin: Origin,
+    /// Used for [`Output::Triangles`].
+    pub triangulation: TriangulationOptions,
+    /// Used for [`Output::ConvexPieces`].
+    pub decomposition: DecompositionOptions,
+}
+
+impl Default for PipelineOptions {
+    fn default() -> Self {
+        Self {
+            alpha_threshold: 1,
+            subpixel: false,
+            algorithm: simplify::Algorithm::default(),
+            epsilon: 1.0,
+            output: Output::default(),
+            origin: Origin::default(),
+            triangulation: TriangulationOptions::default(),
+            decomposition: DecompositionOptions::default(),
+        }
+    }
+}
+
+impl PipelineOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alpha_threshold(mut self, alpha_threshold: u8) -> Self {
+        self.alpha_threshold = alpha_threshold;
+        self
+    }
+
+    pub fn subpixel(mut self, subpixel: bool) -> Self {
+        self.subpixel = subpixel;
+        self
+    }
+
+    pub fn algorithm(mut self, algorithm: simplify::Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    pub fn output(mut self, output: Output) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn triangulation(mut self, triangulation: TriangulationOptions) -> Self {
+        self.triangulation = triangulation;
+        self
+    }
+
+    pub fn decomposition(mut self, decomposition: DecompositionOptions) -> Self {
+        self.decomposition = decomposition;
+        self
+    }
+}
+
+/// The result of a [`Pipeline`], depending on [`PipelineOptions::output`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Hitbox {
+    Triangles(Vec<TriangleF>),
+    ConvexPieces(Vec<Polygon<PointF>>),
+    Polygons(Vec<Shape<PointF>>),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Pipeline {
+    options: PipelineOptions,
+}
+
+impl Pipeline {
+    pub fn new(options: PipelineOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn options(&self) -> &PipelineOptions {
+        &self.options
+    }
+
+    /// Extracts and simplifies the shapes of the image, in the coordinates of the chosen origin.
+    pub fn shapes(&self, image: image::DynamicImage) -> Result<Vec<Shape<PointF>>, Error> {
+        let (width, height) = image.dimensions();
+        let image = ImagePolygon::try_new(image)?
+            .with_options(MaskOptions::new().alpha_threshold(self.options.alpha_threshold));
+        let shapes = if self.options.subpixel {
+            image.to_subpixel_shapes()?
+        } else {
+            image.to_shapes()?.into_iter().map(Shape::from).collect()
+        };
+        Ok(shapes
+            .iter()
+            .map(|shape| self.transform(self.simplify(shape), width, height))
+            .collect())
+    }
+
+    /// Runs the whole pipeline on an image.
+    pub fn run(&self, image: image::DynamicImage) -> Result<Hitbox, Error> {
+        let shapes = self.shapes(image)?;
+        let hitbox = match self.options.output {
+            Output::Triangles => {
+                let mut triangles = vec![];
+                for shape in &shapes {
+                    let options = &self.options.triangulation;
+                    triangles.extend(triangulate(&shape.outer, Some(&shape.holes), options)?);
+                }
+                Hitbox::Triangles(triangles)
+            }
+            Output::ConvexPieces => {
+                let mut pieces = vec![];
+                for shape in &shapes {
+                    let options = &self.options.decomposition;
+                    pieces.extend(hertel_mehlhorn(&shape.outer, Some(&shape.holes), options)?);
+                }
+                Hitbox::ConvexPieces(pieces)
+            }
+            Output::Polygons => Hitbox::Polygons(shapes),
+        };
+        Ok(hitbox)
+    }
+
+    /// [`Pipeline::run`] on PNG or JPEG bytes.
+    pub fn run_bytes(&self, bytes: &[u8]) -> Result<Hitbox, Error> {
+        self.run(image::load_from_memory(bytes).map_err(Error::Decode)?)
+    }
+
+    fn simplify(&self, shape: &Shape<PointF>) -> Shape<PointF> {
+        let epsilon = self.options.epsilon;
+        match self.options.algorithm {
+            simplify::Algorithm::Rdp => simplify::simplify_shape(shape, epsilon),
+            algorithm => {
+                let simplify = |ring| simplify_ring(ring, algorithm, Target::Tolerance(epsilon));
+                Shape {
+                    outer: simplify(&shape.outer),
+                    holes: shape.holes.iter().map(simplify).collect(),
+                }
+            }
+        }
+    }
+
+    fn transform(&self, shape: Shape<PointF>, width: u32, height: u32) -> Shape<PointF> {
+        // Subpixel outlines cover whole pixels, while the others go through the top-left corners
+        // of the pixels, from 0 to the size minus one
+        let extent = |size: u32| {
+            if self.options.subpixel {
+                size as f64
+            } else {
+                size as f64 - 1.0
+            }
+        };
+        let (width, height) = (extent(width), extent(height));
+        let ring = |ring: Polygon<PointF>| -> Polygon<PointF> {
+            match self.options.origin {
+                Origin::TopLeft => ring,
+                Origin::Center => ring
+                    .into_iter()
+                    .map(|point| PointF::new(point.x - width / 2.0, point.y - height / 2.0))
+                    .collect(),
+                Origin::BottomLeft => ring
+                    .into_iter()
+                    .rev()
+                    .map(|point| PointF::new(point.x, height - point.y))
+                    .collect(),
+            }
+        };
+        Shape {
+            outer: ring(shape.outer),
+            holes: shape.holes.into_iter().map(ring).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An 8x6 image with an opaque 4x4 square whose top-left pixel is at (1, 1).
+    fn square_image() -> image::DynamicImage {
+        let mut image = image::RgbaImage::new(8, 6);
+        for y in 1..5 {
+            for x in 1..5 {
+                image.put_pixel(x, y, image::Rgba([0, 0, 0, 255]));
+            }
+        }
+        image::DynamicImage::ImageRgba8(image)
+    }
+
+    fn options() -> PipelineOptions {
+        PipelineOptions::new().subpixel(true).epsilon(0.5)
+    }
+
+    #[test]
+    fn test_outputs() {
+        let run = |output| Pipeline::new(options().output(output)).run(square_image());
+
+        let Ok(Hitbox::Polygons(shapes)) = run(Output::Polygons) else {
+            panic!("expected polygons");
+        };
+        assert_eq!(shapes.len(), 1);
+        let expected = shapes[0].outer.area();
+
+        let Ok(Hitbox::Triangles(triangles)) = run(Output::Triangles) else {
+            panic!("expected triangles");
+        };
+        let area: f64 = triangles.iter().map(TriangleF::area).sum();
+        assert!((area - expected).abs() < 1e-9);
+
+        let Ok(Hitbox::ConvexPieces(pieces)) = run(Output::ConvexPieces) else {
+            panic!("expected convex pieces");
+        };
+        let area: f64 = pieces.iter().map(Polygon::area).sum();
+        assert!((area - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_origin() {
+        let pipeline = |origin| Pipeline::new(options().origin(origin));
+        let top_left = pipeline(Origin::TopLeft).shapes(square_image()).unwrap();
+        let center = pipeline(Origin::Center).shapes(square_image()).unwrap();
+        let bottom_left = pipeline(Origin::BottomLeft).shapes(square_image()).unwrap();
+
+        let bounds = |shapes: &[Shape<PointF>]| shapes[0].outer.bounding_box().unwrap();
+        assert_eq!(
+            bounds(&center).min,
+            bounds(&top_left).min - PointF::new(4.0, 3.0)
+        );
+        let (flipped, original) = (bounds(&bottom_left), bounds(&top_left));
+        assert_eq!(
+            flipped.min,
+            PointF::new(original.min.x, 6.0 - original.max.y)
+        );
+        assert_eq!(
+            bottom_left[0].outer.signed_area(),
+            top_left[0].outer.signed_area()
+        );
+    }
+
+    #[test]
+    fn test_origin_pixel_outlines() {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+            8,
+            6,
+            image::Rgba([0, 0, 0, 255]),
+        ));
+        let bounds = |origin, image| {
+            let pipeline = Pipeline::new(PipelineOptions::new().origin(origin));
+            pipeline.shapes(image).unwrap()[0]
+                .outer
+                .bounding_box()
+                .unwrap()
+        };
+
+        // The whole image, from the first to the last pixel
+        let center = bounds(Origin::Center, image.clone());
+        assert_eq!(
+            (center.min, center.max),
+            (PointF::new(-3.5, -2.5), PointF::new(3.5, 2.5))
+        );
+        let bottom_left = bounds(Origin::BottomLeft, image);
+        assert_eq!(
+            (bottom_left.min, bottom_left.max),
+            (PointF::new(0.0, 0.0), PointF::new(7.0, 5.0))
+        );
+
+        // The bottom row of the square is the second one from the bottom of the image
+        let square = bounds(Origin::BottomLeft, square_image());
+        assert_eq!(
+            (square.min, square.max),
+            (PointF::new(1.0, 1.0), PointF::new(4.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn test_options_serde() {
+        let options: PipelineOptions =
+            serde_json::from_str(r#"{"epsilon": 2.0, "output": "ConvexPieces"}"#).unwrap();
+        assert_eq!(
+            options,
+            PipelineOptions::new()
+                .epsilon(2.0)
+                .output(Output::ConvexPieces)
+        );
+    }
+}
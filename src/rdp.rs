@@ -1,12 +1,22 @@
-use crate::geometry::{distance, Point, Polygon};
+use std::collections::BinaryHeap;
+
+use crate::collision::convex_hull;
+use crate::geometry::{PointF, Polygon, Vertex};
+use crate::simplify::Target;
+
+/// Ramer–Douglas–Peucker simplification of an open polyline, which always keeps its first and
+/// last points. Use [`rdp_closed`] for the rings of a polygon.
+pub fn rdp<P: Vertex>(polygon: &Polygon<P>, epsilon: f64) -> Polygon<P> {
+    if polygon.len() < 3 {
+        return polygon.clone();
+    }
 
-pub fn rdp(polygon: &Polygon, epsilon: f64) -> Polygon {
     let mut d_max = 0.0;
     let mut index = 0;
     let end = polygon.len() - 1;
 
     for i in 1..end {
-        let d = perpendicular_distance(polygon[i], polygon[0], polygon[end]);
+        let d = perpendicular_distance(polygon[i].into(), polygon[0].into(), polygon[end].into());
         if d > d_max {
             index = i;
             d_max = d;
@@ -14,20 +24,234 @@ pub fn rdp(polygon: &Polygon, epsilon: f64) -> Polygon {
     }
 
     if d_max > epsilon {
-        let mut results = rdp(&Vec::from(&polygon[..=index]), epsilon);
+        let mut results = rdp(&Polygon::from(&polygon[..=index]), epsilon);
         results.pop();
-        results.extend(rdp(&Vec::from(&polygon[index..]), epsilon));
+        results.extend(rdp(&Polygon::from(&polygon[index..]), epsilon));
         results
     } else {
-        vec![polygon[0], polygon[end]]
+        Polygon::from(vec![polygon[0], polygon[end]])
+    }
+}
+
+/// Ramer–Douglas–Peucker simplification of a closed ring. The ring is split at its two
+/// farthest-apart points, which are kept, and both halves are simplified. The result starts at one
+/// of these points and keeps at least 3 points.
+pub fn rdp_closed<P: Vertex>(polygon: &Polygon<P>, epsilon: f64) -> Polygon<P> {
+    let mut ring = polygon.clone();
+    let closed = ring.len() > 1 && ring.is_closed();
+    ring.open();
+    if ring.len() <= 3 {
+        return polygon.clone();
+    }
+
+    let points: Vec<PointF> = ring.iter().map(|&point| point.into()).collect();
+    let Some((first, second)) = farthest_apart(&points) else {
+        return polygon.clone();
+    };
+    let mut halves = [
+        Polygon::from(&ring[first..=second]),
+        ring[second..]
+            .iter()
+            .chain(&ring[..=first])
+            .copied()
+            .collect(),
+    ]
+    .map(|half| rdp(&half, epsilon));
+
+    // Both halves reduced to their ends would leave a flat ring
+    if halves.iter().all(|half| half.len() == 2) {
+        let n = ring.len();
+        let farthest = [(first, second), (second, first + n)]
+            .map(|(start, end)| farthest_in_span(&points, start, end));
+        let half = match farthest {
+            [None, _] => 1,
+            [Some((_, d0)), Some((_, d1))] if d1 > d0 => 1,
+            _ => 0,
+        };
+        if let Some((index, _)) = farthest[half] {
+            halves[half].insert(1, ring[index % n]);
+        }
+    }
+
+    let [mut result, second_half] = halves;
+    result.pop();
+    result.extend(&second_half[..second_half.len() - 1]);
+    if closed {
+        result.close();
     }
+    result
 }
 
-fn perpendicular_distance(p: Point, a: Point, b: Point) -> f64 {
-    let num = ((b.y as i32 - a.y as i32) * p.x as i32) - ((b.x as i32 - a.x as i32) * p.y as i32)
-        + (b.x as i32 * a.y as i32)
-        - (b.y as i32 * a.x as i32);
-    let num = num.abs() as f64;
-    let den = distance(a, b) as f64;
-    num / den
+/// [`rdp_closed`] stopped at a [`Target`] instead of a distance: the points farthest from the
+/// simplified ring are added back one at a time until the target is met.
+pub fn rdp_closed_to<P: Vertex>(polygon: &Polygon<P>, target: Target) -> Polygon<P> {
+    if let Target::Tolerance(epsilon) = target {
+        return rdp_closed(polygon, epsilon);
+    }
+    let mut ring = polygon.clone();
+    let closed = ring.len() > 1 && ring.is_closed();
+    ring.open();
+    let n = ring.len();
+    if n <= 3 {
+        return polygon.clone();
+    }
+
+    let points: Vec<PointF> = ring.iter().map(|&point| point.into()).collect();
+    let Some((first, second)) = farthest_apart(&points) else {
+        return polygon.clone();
+    };
+
+    // Spans by decreasing distance of their farthest point, compared through the bits of the
+    // distance which sort like the values for positive numbers
+    let mut spans = BinaryHeap::new();
+    let push = |spans: &mut BinaryHeap<_>, start: usize, end: usize| {
+        if let Some((index, distance)) = farthest_in_span(&points, start, end) {
+            spans.push((distance.to_bits(), start, end, index));
+        }
+    };
+    push(&mut spans, first, second);
+    push(&mut spans, second, first + n);
+
+    let original_area = ring.signed_area();
+    let mut area = 0.0;
+    let mut kept = vec![first, second];
+    loop {
+        let done = match target {
+            Target::VertexCount(count) => kept.len() >= count.max(3),
+            Target::AreaDeviation(max_deviation) => {
+                kept.len() >= 3 && (area - original_area).abs() <= max_deviation
+            }
+            Target::Tolerance(_) => true,
+        };
+        let Some((_, start, end, index)) = spans.pop().filter(|_| !done) else {
+            break;
+        };
+        let (a, b, c) = (points[start % n], points[index % n], points[end % n]);
+        area += (b - a).cross(c - a) / 2.0;
+        kept.push(index % n);
+        push(&mut spans, start, index);
+        push(&mut spans, index, end);
+    }
+
+    kept.sort_unstable();
+    let mut result: Polygon<P> = kept.into_iter().map(|index| ring[index]).collect();
+    if closed {
+        result.close();
+    }
+    result
+}
+
+// The point between `start` and `end` farthest from the segment joining them, with indices
+// wrapping around the ring.
+pub(crate) fn farthest_in_span(
+    points: &[PointF],
+    start: usize,
+    end: usize,
+) -> Option<(usize, f64)> {
+    let n = points.len();
+    let (a, b) = (points[start % n], points[end % n]);
+    (start + 1..end)
+        .map(|i| (i, perpendicular_distance(points[i % n], a, b)))
+        .max_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+}
+
+// Indices of the two points farthest from each other, the smaller one first.
+fn farthest_apart(points: &[PointF]) -> Option<(usize, usize)> {
+    let hull = convex_hull(points.to_vec());
+    let mut best: Option<(f64, PointF, PointF)> = None;
+    for (i, &a) in hull.iter().enumerate() {
+        for &b in &hull[i + 1..] {
+            let distance = a.distance(b);
+            if best.is_none_or(|(max, ..)| distance > max) {
+                best = Some((distance, a, b));
+            }
+        }
+    }
+    let (_, a, b) = best?;
+    let first = points.iter().position(|&point| point == a)?;
+    let second = points.iter().position(|&point| point == b)?;
+    Some((first.min(second), first.max(second)))
+}
+
+pub(crate) fn perpendicular_distance(p: PointF, a: PointF, b: PointF) -> f64 {
+    let den = a.distance(b);
+    if den == 0.0 {
+        return p.distance(a);
+    }
+    (b - a).cross(p - a).abs() / den
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{points, Point};
+
+    #[test]
+    fn test_rdp_short_polylines() {
+        for polygon in [points(&[]), points(&[(1, 1)]), points(&[(1, 1), (4, 5)])] {
+            assert_eq!(rdp(&polygon, 1.0), polygon);
+            assert_eq!(rdp_closed(&polygon, 1.0), polygon);
+        }
+    }
+
+    #[test]
+    fn test_rdp_keeps_ends() {
+        let polyline = points(&[(0, 0), (5, 1), (10, 0), (15, 0), (20, 10)]);
+        assert_eq!(rdp(&polyline, 2.0), points(&[(0, 0), (15, 0), (20, 10)]));
+    }
+
+    #[test]
+    fn test_rdp_closed() {
+        // A square starting in the middle of its top edge, with a small bump on the bottom
+        let square = points(&[
+            (5, 0),
+            (10, 0),
+            (10, 10),
+            (6, 10),
+            (5, 11),
+            (4, 10),
+            (0, 10),
+            (0, 0),
+        ]);
+
+        assert_eq!(rdp(&square, 2.0)[0], Point::new(5, 0));
+        let simplified = rdp_closed(&square, 2.0);
+        assert_eq!(simplified.len(), 4);
+        assert_eq!(simplified.area(), 100.0);
+        assert_eq!(simplified.winding(), square.winding());
+
+        let mut closed = square.clone();
+        closed.close();
+        assert!(rdp_closed(&closed, 2.0).is_closed());
+        assert_eq!(rdp_closed(&closed, 2.0).len(), 5);
+    }
+
+    #[test]
+    fn test_rdp_closed_to_target() {
+        let square = points(&[
+            (5, 0),
+            (10, 0),
+            (10, 10),
+            (6, 10),
+            (5, 11),
+            (4, 10),
+            (0, 10),
+            (0, 0),
+        ]);
+
+        let four = rdp_closed_to(&square, Target::VertexCount(4));
+        assert_eq!(four, points(&[(10, 0), (10, 10), (0, 10), (0, 0)]));
+        assert_eq!(rdp_closed_to(&square, Target::VertexCount(2)).len(), 3);
+        assert_eq!(rdp_closed_to(&square, Target::VertexCount(20)).len(), 8);
+
+        let simplified = rdp_closed_to(&square, Target::AreaDeviation(0.5));
+        assert!((simplified.area() - square.area()).abs() <= 0.5);
+        assert!(simplified.len() < square.len());
+    }
+
+    #[test]
+    fn test_rdp_closed_keeps_three_points() {
+        let sliver = points(&[(0, 0), (5, 1), (10, 0), (5, 0)]);
+        assert_eq!(rdp_closed(&sliver, 100.0).len(), 3);
+    }
 }
@@ -0,0 +1,318 @@
+//! Simplification of a polygon together with its holes that preserves its topology. Unlike
+//! running [`rdp`](crate::rdp::rdp) on each ring, no ring ends up crossing itself or another one,
+//! and the holes stay inside the outer ring and outside of each other.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::*;
+use crate::rdp::{farthest_in_span, rdp_closed_to};
+use crate::visvalingam::visvalingam_closed_to;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Algorithm {
+    /// Ramer–Douglas–Peucker, see [`crate::rdp`].
+    #[default]
+    Rdp,
+    /// Visvalingam–Whyatt, see [`crate::visvalingam`].
+    Visvalingam,
+}
+
+/// When the simplification of a ring stops.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Target {
+    /// The tolerance of the algorithm: a distance for RDP, an area for Visvalingam–Whyatt.
+    Tolerance(f64),
+    /// Exactly this many points, at least 3, or all of them for smaller rings.
+    VertexCount(usize),
+    /// As few points as possible while the area of the ring changes by at most this much.
+    AreaDeviation(f64),
+}
+
+/// Simplifies a closed ring on its own with the given algorithm.
+pub fn simplify_ring<P: Vertex>(
+    ring: &Polygon<P>,
+    algorithm: Algorithm,
+    target: Target,
+) -> Polygon<P> {
+    match algorithm {
+        Algorithm::Rdp => rdp_closed_to(ring, target),
+        Algorithm::Visvalingam => visvalingam_closed_to(ring, target),
+    }
+}
+
+type Segment = (PointF, PointF);
+
+struct Ring {
+    points: Polygon<PointF>,
+    area: f64,
+    // Sorted indices of the points kept. Each of them starts a span that stands for the points up
+    // to the next kept one.
+    kept: Vec<usize>,
+}
+
+impl Ring {
+    fn new(points: Polygon<PointF>, epsilon: f64) -> Self {
+        let n = points.len();
+        let area = points.signed_area();
+        let far = (0..n).max_by(|&i, &j| {
+            let (a, b) = (points[i].distance(points[0]), points[j].distance(points[0]));
+            a.total_cmp(&b)
+        });
+        let mut ring = Self {
+            points,
+            area,
+            kept: (0..n).collect(),
+        };
+        let Some(far) = far.filter(|&far| far != 0 && n > 3) else {
+            return ring;
+        };
+
+        // Douglas-Peucker on both halves of the ring
+        ring.kept = vec![0, far];
+        let mut pending = vec![(0, far), (far, n)];
+        while let Some((start, end)) = pending.pop() {
+            if let Some((index, distance)) = ring.farthest(start, end) {
+                if distance > epsilon {
+                    ring.insert(index % n);
+                    pending.extend([(start, index), (index, end)]);
+                }
+            }
+        }
+        while ring.kept.len() < 3 && ring.refine_widest() {}
+        ring
+    }
+
+    // First and last point of a span, the last one being past `n` for the span that wraps around.
+    fn span(&self, span: usize) -> (usize, usize) {
+        let start = self.kept[span];
+        let end = match self.kept.get(span + 1) {
+            Some(&end) => end,
+            None => self.kept[0] + self.points.len(),
+        };
+        (start, end)
+    }
+
+    fn spans(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.kept.len()).map(|span| self.span(span))
+    }
+
+    fn farthest(&self, start: usize, end: usize) -> Option<(usize, f64)> {
+        farthest_in_span(&self.points, start, end)
+    }
+
+    fn insert(&mut self, index: usize) {
+        if let Err(position) = self.kept.binary_search(&index) {
+            self.kept.insert(position, index);
+        }
+    }
+
+    // The point of the span farthest from it, if the span has any point left.
+    fn refinement(&self, span: usize) -> Option<usize> {
+        let (start, end) = self.span(span);
+        self.farthest(start, end)
+            .map(|(index, _)| index % self.points.len())
+    }
+
+    fn refine(&mut self, span: usize) -> bool {
+        self.refinement(span)
+            .map(|index| self.insert(index))
+            .is_some()
+    }
+
+    fn refine_widest(&mut self) -> bool {
+        let widest = (0..self.kept.len())
+            .filter_map(|span| {
+                let (start, end) = self.span(span);
+                self.farthest(start, end)
+                    .map(|(_, distance)| (span, distance))
+            })
+            .max_by(|(_, d1), (_, d2)| d1.total_cmp(d2));
+        widest.is_some_and(|(span, _)| self.refine(span))
+    }
+
+    fn outline(&self) -> Polygon<PointF> {
+        self.kept.iter().map(|&index| self.points[index]).collect()
+    }
+
+    fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        let n = self.points.len();
+        self.spans()
+            .map(move |(start, end)| (self.points[start], self.points[end % n]))
+    }
+
+    // Whether the simplified ring is flat or turned inside out.
+    fn is_inverted(&self) -> bool {
+        self.area != 0.0 && self.outline().signed_area() * self.area <= 0.0
+    }
+
+    // Spans whose original points surround the given point.
+    fn spans_around(&self, point: PointF) -> Vec<usize> {
+        let n = self.points.len();
+        (0..self.kept.len())
+            .filter(|&span| {
+                let (start, end) = self.span(span);
+                BoundingBox::from_points((start..=end).map(|i| self.points[i % n]))
+                    .is_some_and(|bounds| bounds.contains(point))
+            })
+            .collect()
+    }
+}
+
+fn on_outline(outline: &[PointF], point: PointF) -> bool {
+    outline
+        .iter()
+        .zip(outline.iter().cycle().skip(1))
+        .any(|(&a, &b)| (b - a).cross(point - a) == 0.0 && (a - point).dot(b - point) <= 0.0)
+}
+
+// Spans to refine so that the holes stay inside the outer ring and outside of each other.
+fn misplaced_spans(rings: &[Ring]) -> BTreeSet<(usize, usize)> {
+    let outlines: Vec<Polygon<PointF>> = rings.iter().map(Ring::outline).collect();
+    let bounds: Vec<Option<BoundingBox>> = outlines.iter().map(Polygon::bounding_box).collect();
+
+    let mut spans = BTreeSet::new();
+    for hole in 1..rings.len() {
+        for container in 0..rings.len() {
+            let overlapping = match (bounds[container], bounds[hole]) {
+                (Some(a), Some(b)) => a.intersects(&b),
+                _ => false,
+            };
+            if container == hole || (container != 0 && !overlapping) {
+                continue;
+            }
+            let Some(point) = outlines[hole]
+                .iter()
+                .copied()
+                .find(|&point| !on_outline(&outlines[container], point))
+            else {
+                continue;
+            };
+            let inside = bounds[container].is_some_and(|bounds| bounds.contains(point))
+                && outlines[container].contains_point(point);
+            if inside != (container == 0) {
+                let around = rings[container].spans_around(point);
+                spans.extend(around.into_iter().map(|span| (container, span)));
+            }
+        }
+    }
+    spans
+}
+
+/// Simplifies the outer ring and the holes of a polygon with the Douglas-Peucker algorithm, then
+/// keeps more of the original points wherever the result would cross itself, lose a hole outside
+/// the outer ring or put a hole inside another. Every ring keeps at least 3 points.
+///
+/// Problems already present in the input are left as is.
+pub fn simplify<P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: &[Polygon<P>],
+    epsilon: f64,
+) -> (Polygon<P>, Vec<Polygon<P>>) {
+    let originals: Vec<&Polygon<P>> = std::iter::once(polygon).chain(holes).collect();
+    let mut rings: Vec<Ring> = originals
+        .iter()
+        .map(|ring| Ring::new(ring.iter().map(|&point| point.into()).collect(), epsilon))
+        .collect();
+
+    loop {
+        let mut refined = false;
+        for ring in &mut rings {
+            if ring.is_inverted() {
+                refined |= ring.refine_widest();
+            }
+        }
+
+        let mut spans: BTreeSet<(usize, usize)> = BTreeSet::new();
+
+        let (owners, segments): (Vec<(usize, usize)>, Vec<Segment>) = rings
+            .iter()
+            .enumerate()
+            .flat_map(|(index, ring)| {
+                ring.segments()
+                    .enumerate()
+                    .map(move |(span, segment)| ((index, span), segment))
+            })
+            .unzip();
+        for (i, j) in crossing_pairs(&segments) {
+            spans.extend([owners[i], owners[j]]);
+        }
+        if spans.is_empty() {
+            spans = misplaced_spans(&rings);
+        }
+
+        let points: Vec<(usize, usize)> = spans
+            .into_iter()
+            .filter_map(|(ring, span)| Some((ring, rings[ring].refinement(span)?)))
+            .collect();
+        for &(ring, index) in &points {
+            rings[ring].insert(index);
+        }
+        refined |= !points.is_empty();
+        if !refined {
+            break;
+        }
+    }
+
+    let mut simplified = rings.iter().zip(originals).map(|(ring, original)| {
+        ring.kept
+            .iter()
+            .map(|&index| original[index])
+            .collect::<Polygon<P>>()
+    });
+    let outer = simplified.next().unwrap_or_default();
+    (outer, simplified.collect())
+}
+
+/// [`simplify`] applied to a [`Shape`].
+pub fn simplify_shape<P: Vertex>(shape: &Shape<P>, epsilon: f64) -> Shape<P> {
+    let (outer, holes) = simplify(&shape.outer, &shape.holes, epsilon);
+    Shape { outer, holes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdp::rdp;
+    use crate::triangulation::{triangulate, TriangulationOptions};
+
+    #[test]
+    fn test_hole_stays_inside() {
+        // A rectangle with a small tab on top, and a hole inside the tab
+        let outer = points(&[
+            (0, 10),
+            (40, 10),
+            (40, 0),
+            (60, 0),
+            (60, 10),
+            (100, 10),
+            (100, 60),
+            (0, 60),
+        ]);
+        let hole = points(&[(45, 3), (45, 7), (55, 7), (55, 3)]);
+        let options = TriangulationOptions::default();
+
+        let independent = rdp(&outer, 12.0);
+        assert!(!independent.contains_point(hole[0]));
+
+        let (simplified, holes) = simplify(&outer, std::slice::from_ref(&hole), 12.0);
+        assert!(simplified.len() < outer.len());
+        assert!(holes[0]
+            .iter()
+            .all(|&point| simplified.contains_point(point)));
+        assert!(triangulate(&simplified, Some(&holes), &options).is_ok());
+    }
+
+    #[test]
+    fn test_rings_keep_three_points() {
+        let outer = points(&[(0, 0), (50, 0), (100, 0), (100, 100), (50, 100), (0, 100)]);
+        let hole = points(&[(10, 10), (11, 10), (12, 11), (12, 12), (11, 12), (10, 11)]);
+
+        let (simplified, holes) = simplify(&outer, &[hole], 1000.0);
+
+        assert_eq!(simplified.len(), 3);
+        assert_eq!(holes[0].len(), 3);
+        assert!(holes[0].area() > 0.0);
+    }
+}
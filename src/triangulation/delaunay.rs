@@ -0,0 +1,315 @@
+//! Constrained Delaunay triangulation, obtained by flipping the edges of an existing
+//! triangulation of the polygon, and its refinement with Steiner points.
+//!
+//! The constrained edges are the boundary edges of the triangulation: the edges of the outer
+//! ring and of the holes. They are never flipped, only split by refinement.
+
+use std::collections::HashSet;
+
+use super::TriangulationOptions;
+use crate::geometry::{Mesh, PointF, Vertex};
+
+enum Location {
+    Inside(usize),
+    OnEdge(usize, usize),
+    Vertex,
+    Outside,
+}
+
+pub(super) struct Triangulation {
+    points: Vec<PointF>,
+    // Every triangle is wound with a positive signed area
+    triangles: Vec<[usize; 3]>,
+    // Triangle on the other side of the edge from the `k`-th to the next vertex, `None` on a
+    // constrained edge
+    neighbours: Vec<[Option<usize>; 3]>,
+}
+
+fn orient(a: PointF, b: PointF, c: PointF) -> f64 {
+    (b - a).cross(c - a)
+}
+
+// Whether `d` lies inside the circumcircle of the positively wound triangle `a b c`, with a
+// tolerance so that co-circular points don't make edges flip back and forth.
+fn in_circle(a: PointF, b: PointF, c: PointF, d: PointF) -> bool {
+    let (a, b, c) = (a - d, b - d, c - d);
+    let terms = [
+        a.dot(a) * b.cross(c),
+        b.dot(b) * c.cross(a),
+        c.dot(c) * a.cross(b),
+    ];
+    let magnitude: f64 = terms.iter().map(|term| term.abs()).sum();
+    terms.iter().sum::<f64>() > magnitude * 1e-12
+}
+
+fn circumcenter(a: PointF, b: PointF, c: PointF) -> Option<PointF> {
+    let (b, c) = (b - a, c - a);
+    let denominator = 2.0 * b.cross(c);
+    if denominator == 0.0 {
+        return None;
+    }
+    let x = (c.y * b.dot(b) - b.y * c.dot(c)) / denominator;
+    let y = (b.x * c.dot(c) - c.x * b.dot(b)) / denominator;
+    Some(a + PointF::new(x, y))
+}
+
+fn smallest_angle(a: PointF, b: PointF, c: PointF) -> f64 {
+    [(a, b, c), (b, c, a), (c, a, b)]
+        .iter()
+        .map(|&(corner, p, q)| {
+            let (u, v) = (p - corner, q - corner);
+            u.cross(v).abs().atan2(u.dot(v))
+        })
+        .fold(f64::INFINITY, f64::min)
+        .to_degrees()
+}
+
+impl Triangulation {
+    pub(super) fn new<P: Vertex>(mesh: &Mesh<P>) -> Self {
+        Self {
+            points: mesh.vertices.iter().map(|&vertex| vertex.into()).collect(),
+            triangles: mesh
+                .indices
+                .chunks_exact(3)
+                .map(|corners| [0, 1, 2].map(|k| corners[k] as usize))
+                .collect(),
+            neighbours: mesh
+                .adjacency
+                .iter()
+                .map(|sides| sides.map(|side| side.map(|triangle| triangle as usize)))
+                .collect(),
+        }
+    }
+
+    pub(super) fn into_triangles<P: Vertex>(self) -> Vec<P::Triangle> {
+        let vertices: Vec<P> = self.points.into_iter().map(P::from_point_f).collect();
+        self.triangles
+            .into_iter()
+            .map(|corners| P::Triangle::from(corners.map(|index| vertices[index])))
+            .collect()
+    }
+
+    fn corner(&self, triangle: usize, k: usize) -> PointF {
+        self.points[self.triangles[triangle][k % 3]]
+    }
+
+    // Index of the edge of `triangle` going from `a` to `b`.
+    fn edge_index(&self, triangle: usize, a: usize, b: usize) -> Option<usize> {
+        let corners = self.triangles[triangle];
+        (0..3).find(|&k| corners[k] == a && corners[(k + 1) % 3] == b)
+    }
+
+    fn relink(&mut self, triangle: Option<usize>, a: usize, b: usize, to: usize) {
+        if let Some(triangle) = triangle {
+            if let Some(k) = self.edge_index(triangle, b, a) {
+                self.neighbours[triangle][k] = Some(to);
+            }
+        }
+    }
+
+    fn should_flip(&self, t: usize, k: usize) -> bool {
+        let Some(u) = self.neighbours[t][k] else {
+            return false;
+        };
+        let [a, b] = [self.triangles[t][k], self.triangles[t][(k + 1) % 3]];
+        let Some(j) = self.edge_index(u, b, a) else {
+            return false;
+        };
+        let (pa, pb, pc) = (self.points[a], self.points[b], self.corner(t, k + 2));
+        let pd = self.corner(u, j + 2);
+        orient(pc, pa, pd) > 0.0 && orient(pd, pb, pc) > 0.0 && in_circle(pa, pb, pc, pd)
+    }
+
+    // Replaces the edge `k` of `t` by the other diagonal of the quadrilateral it forms with its
+    // neighbour.
+    fn flip(&mut self, t: usize, k: usize) -> usize {
+        let [a, b, c] = [0, 1, 2].map(|offset| self.triangles[t][(k + offset) % 3]);
+        let u = self.neighbours[t][k].expect("flipped edge has a neighbour");
+        let j = self.edge_index(u, b, a).expect("neighbours share the edge");
+        let d = self.triangles[u][(j + 2) % 3];
+
+        let (n_bc, n_ca) = (
+            self.neighbours[t][(k + 1) % 3],
+            self.neighbours[t][(k + 2) % 3],
+        );
+        let (n_ad, n_db) = (
+            self.neighbours[u][(j + 1) % 3],
+            self.neighbours[u][(j + 2) % 3],
+        );
+
+        self.triangles[t] = [c, a, d];
+        self.neighbours[t] = [n_ca, n_ad, Some(u)];
+        self.triangles[u] = [d, b, c];
+        self.neighbours[u] = [n_db, n_bc, Some(t)];
+        self.relink(n_ad, a, d, t);
+        self.relink(n_bc, b, c, u);
+        u
+    }
+
+    // Lawson flips until every unconstrained edge around the pending triangles is Delaunay.
+    fn legalize(&mut self, mut pending: Vec<usize>) {
+        let mut flips_left = 4 * self.triangles.len().pow(2) + 64;
+        while let Some(t) = pending.pop() {
+            if let Some(k) = (0..3).find(|&k| self.should_flip(t, k)) {
+                if flips_left == 0 {
+                    return;
+                }
+                flips_left -= 1;
+                let u = self.flip(t, k);
+                pending.extend([t, u]);
+            }
+        }
+    }
+
+    pub(super) fn make_delaunay(&mut self) {
+        self.legalize((0..self.triangles.len()).collect());
+    }
+
+    fn locate(&self, point: PointF) -> Location {
+        for t in 0..self.triangles.len() {
+            let sides = [0, 1, 2].map(|k| orient(self.corner(t, k), self.corner(t, k + 1), point));
+            if sides.iter().any(|&side| side < 0.0) {
+                continue;
+            }
+            let on: Vec<usize> = (0..3).filter(|&k| sides[k] == 0.0).collect();
+            return match on[..] {
+                [] => Location::Inside(t),
+                [k] => Location::OnEdge(t, k),
+                _ => Location::Vertex,
+            };
+        }
+        Location::Outside
+    }
+
+    fn push_point(&mut self, point: PointF) -> usize {
+        self.points.push(point);
+        self.points.len() - 1
+    }
+
+    fn insert_inside(&mut self, t: usize, point: PointF) {
+        let p = self.push_point(point);
+        let [a, b, c] = self.triangles[t];
+        let [n_ab, n_bc, n_ca] = self.neighbours[t];
+        let (t1, t2) = (self.triangles.len(), self.triangles.len() + 1);
+
+        self.triangles[t] = [a, b, p];
+        self.neighbours[t] = [n_ab, Some(t1), Some(t2)];
+        self.triangles.push([b, c, p]);
+        self.neighbours.push([n_bc, Some(t2), Some(t)]);
+        self.triangles.push([c, a, p]);
+        self.neighbours.push([n_ca, Some(t), Some(t1)]);
+        self.relink(n_bc, b, c, t1);
+        self.relink(n_ca, c, a, t2);
+
+        self.legalize(vec![t, t1, t2]);
+    }
+
+    fn insert_on_edge(&mut self, t: usize, k: usize, point: PointF) {
+        let p = self.push_point(point);
+        let [a, b, c] = [0, 1, 2].map(|offset| self.triangles[t][(k + offset) % 3]);
+        let (n_bc, n_ca) = (
+            self.neighbours[t][(k + 1) % 3],
+            self.neighbours[t][(k + 2) % 3],
+        );
+        let t1 = self.triangles.len();
+        let mut pending = vec![t, t1];
+
+        let u = self.neighbours[t][k];
+        let u1 = u.map(|_| t1 + 1);
+        self.triangles[t] = [a, p, c];
+        self.neighbours[t] = [u1, Some(t1), n_ca];
+        self.triangles.push([p, b, c]);
+        self.neighbours.push([u, n_bc, Some(t)]);
+        self.relink(n_bc, b, c, t1);
+
+        if let (Some(u), Some(u1)) = (u, u1) {
+            let j = self.edge_index(u, b, a).expect("neighbours share the edge");
+            let d = self.triangles[u][(j + 2) % 3];
+            let (n_ad, n_db) = (
+                self.neighbours[u][(j + 1) % 3],
+                self.neighbours[u][(j + 2) % 3],
+            );
+            self.triangles[u] = [b, p, d];
+            self.neighbours[u] = [Some(t1), Some(u1), n_db];
+            self.triangles.push([p, a, d]);
+            self.neighbours.push([Some(t), n_ad, Some(u)]);
+            self.relink(n_ad, a, d, u1);
+            pending.extend([u, u1]);
+        }
+
+        self.legalize(pending);
+    }
+
+    fn is_bad(&self, t: usize, options: &TriangulationOptions) -> bool {
+        let [a, b, c] = [0, 1, 2].map(|k| self.corner(t, k));
+        let area = orient(a, b, c) / 2.0;
+        if area <= 0.0 {
+            return false;
+        }
+        options.max_area.is_some_and(|max_area| area > max_area)
+            || options
+                .min_angle
+                .is_some_and(|min_angle| smallest_angle(a, b, c) < min_angle)
+    }
+
+    // A constrained edge whose diametral circle contains the point.
+    fn encroached(&self, point: PointF) -> Option<(usize, usize)> {
+        (0..self.triangles.len())
+            .flat_map(|t| (0..3).map(move |k| (t, k)))
+            .find(|&(t, k)| {
+                self.neighbours[t][k].is_none()
+                    && (self.corner(t, k) - point).dot(self.corner(t, k + 1) - point) < 0.0
+            })
+    }
+
+    /// Inserts Steiner points at the circumcenters of the triangles that break the limits of the
+    /// options, splitting the constrained edges they would encroach upon instead. `round` maps
+    /// the new points to the vertex type of the output.
+    pub(super) fn refine(
+        &mut self,
+        options: &TriangulationOptions,
+        round: impl Fn(PointF) -> PointF,
+    ) {
+        let key = |corners: [usize; 3]| {
+            let mut corners = corners;
+            corners.sort_unstable();
+            corners
+        };
+        let mut skipped: HashSet<[usize; 3]> = HashSet::new();
+
+        for _ in 0..options.max_steiner_points {
+            let Some(t) = (0..self.triangles.len())
+                .find(|&t| !skipped.contains(&key(self.triangles[t])) && self.is_bad(t, options))
+            else {
+                return;
+            };
+            let [a, b, c] = [0, 1, 2].map(|k| self.corner(t, k));
+            let Some(center) = circumcenter(a, b, c) else {
+                skipped.insert(key(self.triangles[t]));
+                continue;
+            };
+
+            if let Some((s, k)) = self.encroached(center) {
+                let (start, end) = (self.corner(s, k), self.corner(s, k + 1));
+                let middle = round((start + end) / 2.0);
+                let length = (end - start).dot(end - start);
+                let on_segment = orient(start, end, middle).abs() <= length * 1e-12
+                    && (start - middle).dot(end - middle) < 0.0;
+                if on_segment {
+                    self.insert_on_edge(s, k, middle);
+                } else {
+                    skipped.insert(key(self.triangles[t]));
+                }
+                continue;
+            }
+
+            match self.locate(round(center)) {
+                Location::Inside(s) => self.insert_inside(s, round(center)),
+                Location::OnEdge(s, k) => self.insert_on_edge(s, k, round(center)),
+                Location::Vertex | Location::Outside => {
+                    skipped.insert(key(self.triangles[t]));
+                }
+            }
+        }
+    }
+}
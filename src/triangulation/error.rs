@@ -0,0 +1,73 @@
+use std::fmt;
+
+/// A ring of the polygon given to [`super::triangulate`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ring {
+    Outer,
+    /// The hole at this index in the list of holes.
+    Hole(usize),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriangulationErrorKind {
+    TooFewPoints,
+    /// Two edges cross each other. Rings may touch at a vertex but not cross.
+    SelfIntersection,
+    ZeroArea,
+    HoleOutsideOuter,
+    /// The input passed the checks above but the triangulation still failed.
+    Unknown,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TriangulationError {
+    pub ring: Ring,
+    pub kind: TriangulationErrorKind,
+}
+
+/// A hole left out of the triangulation instead of failing it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiscardedHole {
+    pub index: usize,
+    pub reason: TriangulationErrorKind,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TriangulationReport {
+    pub discarded_holes: Vec<DiscardedHole>,
+}
+
+impl fmt::Display for Ring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ring::Outer => write!(f, "outer ring"),
+            Ring::Hole(index) => write!(f, "hole {}", index),
+        }
+    }
+}
+
+impl fmt::Display for TriangulationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriangulationErrorKind::TooFewPoints => write!(f, "has fewer than 3 points"),
+            TriangulationErrorKind::SelfIntersection => write!(f, "has crossing edges"),
+            TriangulationErrorKind::ZeroArea => write!(f, "has a zero area"),
+            TriangulationErrorKind::HoleOutsideOuter => write!(f, "is outside the outer ring"),
+            TriangulationErrorKind::Unknown => write!(f, "could not be triangulated"),
+        }
+    }
+}
+
+impl fmt::Display for TriangulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.ring, self.kind)
+    }
+}
+
+impl std::error::Error for TriangulationError {}
+
+impl TriangulationError {
+    pub(super) fn new(ring: Ring, kind: TriangulationErrorKind) -> Self {
+        Self { ring, kind }
+    }
+}
@@ -0,0 +1,548 @@
+mod delaunay;
+mod error;
+
+use crate::geometry::*;
+use delaunay::Triangulation;
+use earcutr::earcut;
+pub use error::{
+    DiscardedHole, Ring, TriangulationError, TriangulationErrorKind, TriangulationReport,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Algorithm {
+    /// Ear clipping, fast but prone to thin triangles.
+    #[default]
+    Earcut,
+    /// Constrained Delaunay triangulation, which maximizes the smallest angle while keeping every
+    /// edge of the outer ring and of the holes.
+    ConstrainedDelaunay,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TriangulationOptions {
+    pub algorithm: Algorithm,
+    /// Smallest angle allowed in a triangle, in degrees. Values above about 30 may not be
+    /// reachable and stop at `max_steiner_points`. Only used by [`Algorithm::ConstrainedDelaunay`].
+    pub min_angle: Option<f64>,
+    /// Largest area allowed for a triangle. Only used by [`Algorithm::ConstrainedDelaunay`].
+    pub max_area: Option<f64>,
+    /// Maximum number of points added by the refinement.
+    pub max_steiner_points: usize,
+}
+
+impl Default for TriangulationOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::default(),
+            min_angle: None,
+            max_area: None,
+            max_steiner_points: 1000,
+        }
+    }
+}
+
+impl TriangulationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    pub fn delaunay() -> Self {
+        Self::new().algorithm(Algorithm::ConstrainedDelaunay)
+    }
+
+    pub fn min_angle(mut self, degrees: f64) -> Self {
+        self.min_angle = Some(degrees);
+        self
+    }
+
+    pub fn max_area(mut self, area: f64) -> Self {
+        self.max_area = Some(area);
+        self
+    }
+
+    pub fn max_steiner_points(mut self, count: usize) -> Self {
+        self.max_steiner_points = count;
+        self
+    }
+}
+
+/// Triangulates the polygon and its holes. With the Delaunay algorithm and integer points,
+/// the points added by the refinement are rounded to the pixel grid.
+///
+/// Holes with fewer than 3 points or a zero area are left out, see [`triangulate_with_report`]
+/// to know which ones.
+pub fn triangulate<P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: Option<&Vec<Polygon<P>>>,
+    options: &TriangulationOptions,
+) -> Result<Vec<P::Triangle>, TriangulationError> {
+    triangulate_with_report(polygon, holes, options).map(|(triangles, _)| triangles)
+}
+
+/// Same as [`triangulate`], also reporting the holes that were left out.
+pub fn triangulate_with_report<P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: Option<&Vec<Polygon<P>>>,
+    options: &TriangulationOptions,
+) -> Result<(Vec<P::Triangle>, TriangulationReport), TriangulationError> {
+    let (holes, report) = validate(polygon, holes)?;
+    let (points, indices) = earcut_indices(polygon, &holes)?;
+
+    let triangles = match options.algorithm {
+        Algorithm::Earcut => indices
+            .chunks(3)
+            .map(|chunk| P::Triangle::from([points[chunk[0]], points[chunk[1]], points[chunk[2]]]))
+            .collect(),
+        Algorithm::ConstrainedDelaunay => {
+            let mut triangulation = Triangulation::new(&Mesh::new(points, &indices));
+            triangulation.make_delaunay();
+            triangulation.refine(options, |point| P::from_point_f(point).into());
+            triangulation.into_triangles::<P>()
+        }
+    };
+    Ok((triangles, report))
+}
+
+/// Same as [`triangulate`], but keeps the triangles indexed into a [`Mesh`] with shared vertices
+/// and adjacency.
+pub fn triangulate_indexed<P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: Option<&Vec<Polygon<P>>>,
+    options: &TriangulationOptions,
+) -> Result<Mesh<P>, TriangulationError> {
+    match options.algorithm {
+        Algorithm::Earcut => {
+            let (holes, _) = validate(polygon, holes)?;
+            let (points, indices) = earcut_indices(polygon, &holes)?;
+            Ok(Mesh::new(points, &indices))
+        }
+        Algorithm::ConstrainedDelaunay => {
+            Ok(Mesh::from_triangles(&triangulate(polygon, holes, options)?))
+        }
+    }
+}
+
+fn ring_points<P: Vertex>(ring: &Polygon<P>) -> Vec<PointF> {
+    ring.iter().map(|&point| point.into()).collect()
+}
+
+// Checks the rings, and returns the holes to triangulate with a report of the others.
+fn validate<'a, P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: Option<&'a Vec<Polygon<P>>>,
+) -> Result<(Vec<&'a Polygon<P>>, TriangulationReport), TriangulationError> {
+    use TriangulationErrorKind::*;
+
+    if polygon.len() < 3 {
+        return Err(TriangulationError::new(Ring::Outer, TooFewPoints));
+    }
+    if polygon.signed_area() == 0.0 {
+        return Err(TriangulationError::new(Ring::Outer, ZeroArea));
+    }
+
+    let mut report = TriangulationReport::default();
+    let mut kept: Vec<(usize, &Polygon<P>)> = vec![];
+    for (index, hole) in holes.into_iter().flatten().enumerate() {
+        let reason = if hole.len() < 3 {
+            TooFewPoints
+        } else if hole.signed_area() == 0.0 {
+            ZeroArea
+        } else {
+            kept.push((index, hole));
+            continue;
+        };
+        report.discarded_holes.push(DiscardedHole { index, reason });
+    }
+
+    let ring_of = |ring: usize| match ring {
+        0 => Ring::Outer,
+        _ => Ring::Hole(kept[ring - 1].0),
+    };
+    let rings: Vec<Vec<PointF>> = std::iter::once(ring_points(polygon))
+        .chain(kept.iter().map(|(_, hole)| ring_points(hole)))
+        .collect();
+    if let Some((first, second)) = first_crossing(&rings) {
+        let kind = match first {
+            0 if second != 0 => HoleOutsideOuter,
+            _ => SelfIntersection,
+        };
+        return Err(TriangulationError::new(ring_of(second), kind));
+    }
+
+    // Without crossings, a hole is inside as soon as one of its points not on the outer ring is
+    let on_outer = |point: PointF| {
+        rings[0]
+            .iter()
+            .zip(rings[0].iter().cycle().skip(1))
+            .any(|(&a, &b)| (b - a).cross(point - a) == 0.0 && (a - point).dot(b - point) <= 0.0)
+    };
+    for (ring, points) in rings.iter().enumerate().skip(1) {
+        let outside = points
+            .iter()
+            .find(|&&point| !on_outer(point))
+            .is_some_and(|&point| !polygon.contains_point(point));
+        if outside {
+            return Err(TriangulationError::new(ring_of(ring), HoleOutsideOuter));
+        }
+    }
+
+    Ok((kept.into_iter().map(|(_, hole)| hole).collect(), report))
+}
+
+// Points of the outer ring followed by the holes, and the indices of the triangles into them.
+fn earcut_indices<P: Vertex>(
+    polygon: &Polygon<P>,
+    holes: &[&Polygon<P>],
+) -> Result<(Vec<P>, Vec<usize>), TriangulationError> {
+    let coordinates = |point: &P| {
+        let point: PointF = (*point).into();
+        [point.x, point.y]
+    };
+    let mut points: Vec<f64> = polygon.iter().flat_map(coordinates).collect();
+    let mut holes_indices: Vec<usize> = vec![]; // indices of the first point of each hole in the points array
+
+    let mut polygon_with_holes = polygon.clone().into_vec();
+    for hole in holes {
+        points.extend(hole.iter().flat_map(coordinates));
+        holes_indices.push(points.len() / 2 - hole.len());
+        polygon_with_holes.extend(hole.iter());
+    }
+
+    let indices = earcut(&points, &holes_indices, 2)
+        .map_err(|_| TriangulationError::new(Ring::Outer, TriangulationErrorKind::Unknown))?;
+    Ok((polygon_with_holes, indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triangulate() {
+        let polygon = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 4 },
+            Point { x: 4, y: 0 },
+            Point { x: 2, y: 2 },
+        ]);
+
+        let triangles = match triangulate(&polygon, None, &TriangulationOptions::default()) {
+            Ok(triangles) => triangles,
+            Err(_) => panic!("Error"),
+        };
+
+        assert_eq!(triangles.len(), polygon.len() - 2);
+    }
+
+    #[test]
+    fn test_triangulate_2() {
+        let polygon = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 4 },
+            Point { x: 4, y: 0 },
+            Point { x: 6, y: 1 },
+            Point { x: 7, y: 0 },
+            Point { x: 7, y: 3 },
+            Point { x: 6, y: 5 },
+            Point { x: 1, y: 8 },
+        ]);
+
+        let triangles = triangulate(&polygon, None, &TriangulationOptions::default()).unwrap();
+
+        assert_eq!(triangles.len(), polygon.len() - 2);
+    }
+
+    #[test]
+    fn test_triangulate_3() {
+        let polygon = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 5, y: 2 },
+            Point { x: 8, y: 1 },
+            Point { x: 10, y: 6 },
+            Point { x: 6, y: 4 },
+            Point { x: 10, y: 8 },
+            Point { x: 6, y: 10 },
+            Point { x: 6, y: 16 },
+            Point { x: 5, y: 14 },
+            Point { x: 4, y: 15 },
+            Point { x: 0, y: 16 },
+            Point { x: 2, y: 8 },
+        ]);
+
+        let triangles = triangulate(&polygon, None, &TriangulationOptions::default()).unwrap();
+
+        assert_eq!(triangles.len(), polygon.len() - 2);
+    }
+
+    #[test]
+    fn test_triangulate_triangle() {
+        let polygon = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 3, y: 4 },
+            Point { x: 4, y: 0 },
+        ]);
+        let triangles = match triangulate(&polygon, None, &TriangulationOptions::default()) {
+            Ok(triangles) => triangles,
+            Err(_) => panic!("Error"),
+        };
+
+        assert_eq!(triangles.len(), 1);
+        assert_eq!(triangles[0], Triangle(polygon[0], polygon[1], polygon[2]));
+    }
+
+    #[test]
+    fn test_triangulate_float_polygon_with_hole() {
+        let polygon = Polygon::from(vec![
+            PointF { x: -2.5, y: -2.5 },
+            PointF { x: 2.5, y: -2.5 },
+            PointF { x: 2.5, y: 2.5 },
+            PointF { x: -2.5, y: 2.5 },
+        ]);
+        let holes = vec![Polygon::from(vec![
+            PointF { x: -1.0, y: -1.0 },
+            PointF { x: -1.0, y: 1.0 },
+            PointF { x: 1.0, y: 1.0 },
+            PointF { x: 1.0, y: -1.0 },
+        ])];
+
+        let triangles =
+            triangulate(&polygon, Some(&holes), &TriangulationOptions::default()).unwrap();
+
+        assert_eq!(triangles.len(), 8);
+        assert!(triangles
+            .iter()
+            .all(|triangle| !is_point_inside_triangle(triangle, PointF { x: 0.0, y: 0.0 })));
+    }
+
+    #[test]
+    fn test_triangulate_indexed() {
+        let polygon = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 10, y: 0 },
+            Point { x: 10, y: 10 },
+            Point { x: 0, y: 10 },
+        ]);
+        let holes = vec![Polygon::from(vec![
+            Point { x: 3, y: 3 },
+            Point { x: 3, y: 7 },
+            Point { x: 7, y: 7 },
+            Point { x: 7, y: 3 },
+        ])];
+
+        let mesh =
+            triangulate_indexed(&polygon, Some(&holes), &TriangulationOptions::default()).unwrap();
+
+        assert_eq!(mesh.vertices.len(), 8);
+        assert_eq!(mesh.triangle_count(), 8);
+        assert!(mesh.indices.iter().all(|&index| index < 8));
+        // Only the 8 edges of the rings have no neighbour
+        let linked = mesh.adjacency.iter().flatten().flatten().count();
+        assert_eq!(linked, 8 * 3 - 8);
+        assert_eq!(
+            mesh.triangles()
+                .map(|triangle| triangle.area())
+                .sum::<f64>(),
+            84.0
+        );
+    }
+
+    fn smallest_angle<P: Vertex>(triangles: &[P::Triangle]) -> f64 {
+        triangles
+            .iter()
+            .flat_map(|&triangle| {
+                let [a, b, c]: [P; 3] = triangle.into();
+                let [a, b, c]: [PointF; 3] = [a.into(), b.into(), c.into()];
+                [(a, b, c), (b, c, a), (c, a, b)].map(|(corner, p, q)| {
+                    let (u, v) = (p - corner, q - corner);
+                    u.cross(v).abs().atan2(u.dot(v)).to_degrees()
+                })
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn total_area(triangles: &[TriangleF]) -> f64 {
+        triangles.iter().map(TriangleF::area).sum()
+    }
+
+    #[test]
+    fn test_delaunay_keeps_edges_and_improves_angles() {
+        // A fan of nearly collinear points along the bottom, which earcut cuts into slivers
+        let mut polygon: Polygon<PointF> = (0..=10)
+            .map(|x| PointF::new(x as f64, (x as f64 * 0.3).sin() * 0.2))
+            .collect();
+        polygon.push(PointF::new(5.0, 3.0));
+
+        let earcut = triangulate(&polygon, None, &TriangulationOptions::default()).unwrap();
+        let delaunay = triangulate(&polygon, None, &TriangulationOptions::delaunay()).unwrap();
+
+        assert_eq!(delaunay.len(), earcut.len());
+        assert!((total_area(&delaunay) - polygon.area()).abs() < 1e-9);
+        assert!(smallest_angle::<PointF>(&delaunay) >= smallest_angle::<PointF>(&earcut));
+        let mesh = Mesh::from_triangles(&delaunay);
+        let boundary = mesh
+            .adjacency
+            .iter()
+            .flatten()
+            .filter(|side| side.is_none());
+        assert_eq!(boundary.count(), polygon.len());
+    }
+
+    #[test]
+    fn test_delaunay_refinement() {
+        let rectangle = Polygon::from(vec![
+            PointF::new(0.0, 0.0),
+            PointF::new(10.0, 0.0),
+            PointF::new(10.0, 1.0),
+            PointF::new(0.0, 1.0),
+        ]);
+        let options = TriangulationOptions::delaunay()
+            .min_angle(20.0)
+            .max_area(0.5);
+
+        let triangles = triangulate(&rectangle, None, &options).unwrap();
+
+        assert!((total_area(&triangles) - 10.0).abs() < 1e-9);
+        assert!(triangles.iter().all(|triangle| triangle.area() <= 0.5));
+        assert!(smallest_angle::<PointF>(&triangles) >= 20.0);
+    }
+
+    #[test]
+    fn test_delaunay_refinement_on_integer_points() {
+        let polygon = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 40, y: 0 },
+            Point { x: 40, y: 40 },
+            Point { x: 0, y: 40 },
+        ]);
+        let holes = vec![Polygon::from(vec![
+            Point { x: 10, y: 10 },
+            Point { x: 10, y: 30 },
+            Point { x: 30, y: 30 },
+            Point { x: 30, y: 10 },
+        ])];
+        let options = TriangulationOptions::delaunay().max_area(50.0);
+
+        let triangles = triangulate(&polygon, Some(&holes), &options).unwrap();
+
+        let area: f64 = triangles.iter().map(Triangle::area).sum();
+        assert_eq!(area, 1200.0);
+        assert!(triangles.len() > 8);
+        assert!(triangles.iter().all(|triangle| triangle.area() <= 50.0));
+    }
+
+    #[test]
+    fn test_triangulate_errors() {
+        let options = TriangulationOptions::default();
+        let square = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 4, y: 4 },
+            Point { x: 0, y: 4 },
+        ]);
+        let error = |ring, kind| Err(TriangulationError { ring, kind });
+
+        let line = Polygon::from(vec![Point { x: 0, y: 0 }, Point { x: 4, y: 0 }]);
+        assert_eq!(
+            triangulate(&line, None, &options),
+            error(Ring::Outer, TriangulationErrorKind::TooFewPoints)
+        );
+
+        let flat = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 2, y: 0 },
+            Point { x: 4, y: 0 },
+        ]);
+        assert_eq!(
+            triangulate(&flat, None, &options),
+            error(Ring::Outer, TriangulationErrorKind::ZeroArea)
+        );
+
+        let bow_tie = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 4 },
+            Point { x: 4, y: 0 },
+            Point { x: 0, y: 6 },
+        ]);
+        assert_eq!(
+            triangulate(&bow_tie, None, &options),
+            error(Ring::Outer, TriangulationErrorKind::SelfIntersection)
+        );
+
+        let inside = Polygon::from(vec![
+            Point { x: 1, y: 1 },
+            Point { x: 1, y: 2 },
+            Point { x: 2, y: 2 },
+        ]);
+        let outside = Polygon::from(vec![
+            Point { x: 5, y: 5 },
+            Point { x: 5, y: 6 },
+            Point { x: 6, y: 6 },
+        ]);
+        assert_eq!(
+            triangulate(&square, Some(&vec![inside.clone(), outside]), &options),
+            error(Ring::Hole(1), TriangulationErrorKind::HoleOutsideOuter)
+        );
+
+        let crossing = Polygon::from(vec![
+            Point { x: 3, y: 1 },
+            Point { x: 3, y: 2 },
+            Point { x: 6, y: 2 },
+        ]);
+        assert_eq!(
+            triangulate(&square, Some(&vec![inside, crossing]), &options),
+            error(Ring::Hole(1), TriangulationErrorKind::HoleOutsideOuter)
+        );
+    }
+
+    #[test]
+    fn test_triangulate_with_report() {
+        let square = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 4, y: 0 },
+            Point { x: 4, y: 4 },
+            Point { x: 0, y: 4 },
+        ]);
+        // Touches the outer ring at one corner, which is allowed
+        let hole = Polygon::from(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 1, y: 2 },
+            Point { x: 2, y: 1 },
+        ]);
+        let holes = vec![
+            Polygon::from(vec![Point { x: 1, y: 1 }]),
+            hole,
+            Polygon::from(vec![
+                Point { x: 1, y: 3 },
+                Point { x: 2, y: 3 },
+                Point { x: 3, y: 3 },
+            ]),
+        ];
+
+        let (triangles, report) =
+            triangulate_with_report(&square, Some(&holes), &TriangulationOptions::default())
+                .unwrap();
+
+        assert_eq!(triangles.iter().map(Triangle::area).sum::<f64>(), 14.5);
+        assert_eq!(
+            report.discarded_holes,
+            [
+                DiscardedHole {
+                    index: 0,
+                    reason: TriangulationErrorKind::TooFewPoints
+                },
+                DiscardedHole {
+                    index: 2,
+                    reason: TriangulationErrorKind::ZeroArea
+                },
+            ]
+        );
+    }
+}
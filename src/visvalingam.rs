@@ -0,0 +1,170 @@
+//! Visvalingam–Whyatt simplification, which repeatedly removes the point forming the smallest
+//! triangle with its neighbours. It tends to keep the overall shape better than
+//! [`rdp`](crate::rdp::rdp), at the cost of a less intuitive tolerance: an area instead of a
+//! distance.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::geometry::{PointF, Polygon, Vertex};
+use crate::simplify::Target;
+
+fn triangle_area(a: PointF, b: PointF, c: PointF) -> f64 {
+    (b - a).cross(c - a) / 2.0
+}
+
+// Removes points in order of increasing effective area while `keep_going` accepts the number of
+// points left and the signed area of the triangle about to be removed. Returns which points are
+// kept.
+fn eliminate(
+    points: &[PointF],
+    closed: bool,
+    mut keep_going: impl FnMut(usize, f64) -> bool,
+) -> Vec<bool> {
+    let n = points.len();
+    let minimum = if closed { 3 } else { 2 };
+    let mut kept = vec![true; n];
+    if n <= minimum {
+        return kept;
+    }
+
+    let mut previous: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let removable = |i: usize| closed || (i != 0 && i != n - 1);
+    let area = |previous: &[usize], next: &[usize], i: usize| {
+        triangle_area(points[previous[i]], points[i], points[next[i]])
+    };
+
+    // Areas are compared through their bits, which sort like the values for positive numbers.
+    // Entries whose version is outdated are skipped when popped.
+    let mut versions = vec![0u32; n];
+    let mut heap = BinaryHeap::new();
+    for i in (0..n).filter(|&i| removable(i)) {
+        let key = area(&previous, &next, i).abs().to_bits();
+        heap.push(Reverse((key, i, 0)));
+    }
+
+    let mut left = n;
+    while let Some(Reverse((_, i, version))) = heap.pop() {
+        if !kept[i] || version != versions[i] {
+            continue;
+        }
+        if left <= minimum || !keep_going(left, area(&previous, &next, i)) {
+            break;
+        }
+
+        kept[i] = false;
+        left -= 1;
+        let (p, q) = (previous[i], next[i]);
+        next[p] = q;
+        previous[q] = p;
+        for neighbour in [p, q].into_iter().filter(|&j| removable(j)) {
+            versions[neighbour] += 1;
+            let key = area(&previous, &next, neighbour).abs().to_bits();
+            heap.push(Reverse((key, neighbour, versions[neighbour])));
+        }
+    }
+    kept
+}
+
+fn collect<P: Vertex>(polygon: &[P], kept: &[bool]) -> Polygon<P> {
+    polygon
+        .iter()
+        .zip(kept)
+        .filter(|(_, &kept)| kept)
+        .map(|(&point, _)| point)
+        .collect()
+}
+
+fn points<P: Vertex>(polygon: &[P]) -> Vec<PointF> {
+    polygon.iter().map(|&point| point.into()).collect()
+}
+
+/// Simplifies an open polyline, keeping its first and last points, by removing every point whose
+/// effective area is below `min_area`.
+pub fn visvalingam<P: Vertex>(polygon: &Polygon<P>, min_area: f64) -> Polygon<P> {
+    let kept = eliminate(&points(polygon), false, |_, area| area.abs() < min_area);
+    collect(polygon, &kept)
+}
+
+/// Simplifies a closed ring by removing every point whose effective area is below `min_area`,
+/// keeping at least 3 points.
+pub fn visvalingam_closed<P: Vertex>(polygon: &Polygon<P>, min_area: f64) -> Polygon<P> {
+    visvalingam_closed_to(polygon, Target::Tolerance(min_area))
+}
+
+/// [`visvalingam_closed`] stopped at a [`Target`] instead of an area.
+pub fn visvalingam_closed_to<P: Vertex>(polygon: &Polygon<P>, target: Target) -> Polygon<P> {
+    let mut ring = polygon.clone();
+    let closed = ring.len() > 1 && ring.is_closed();
+    ring.open();
+
+    // Removing a point changes the signed area of the ring by the opposite of its triangle
+    let mut deviation = 0.0;
+    let kept = eliminate(&points(&ring), true, |left, area| match target {
+        Target::Tolerance(min_area) => area.abs() < min_area,
+        Target::VertexCount(count) => left > count,
+        Target::AreaDeviation(max_deviation) => {
+            let accepted = (deviation - area).abs() <= max_deviation;
+            if accepted {
+                deviation -= area;
+            }
+            accepted
+        }
+    });
+
+    let mut result = collect(&ring, &kept);
+    if closed {
+        result.close();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::points;
+
+    #[test]
+    fn test_visvalingam_open() {
+        let polyline = points(&[(0, 0), (5, 1), (10, 0), (15, 0), (20, 10)]);
+
+        assert_eq!(
+            visvalingam(&polyline, 10.0),
+            points(&[(0, 0), (15, 0), (20, 10)])
+        );
+        assert_eq!(visvalingam(&polyline, 1000.0).len(), 2);
+        assert_eq!(visvalingam(&points(&[(1, 1)]), 1.0).len(), 1);
+    }
+
+    #[test]
+    fn test_visvalingam_closed_targets() {
+        let square = points(&[
+            (5, 0),
+            (10, 0),
+            (10, 10),
+            (6, 10),
+            (5, 11),
+            (4, 10),
+            (0, 10),
+            (0, 0),
+        ]);
+
+        assert_eq!(
+            visvalingam_closed(&square, 2.0),
+            points(&[(10, 0), (10, 10), (0, 10), (0, 0)])
+        );
+        assert_eq!(
+            visvalingam_closed_to(&square, Target::VertexCount(5)).len(),
+            5
+        );
+        assert_eq!(
+            visvalingam_closed_to(&square, Target::VertexCount(1)).len(),
+            3
+        );
+
+        let simplified = visvalingam_closed_to(&square, Target::AreaDeviation(0.5));
+        assert_eq!(simplified.len(), 7);
+        assert!((simplified.area() - square.area()).abs() <= 0.5);
+    }
+}
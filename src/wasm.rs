@@ -0,0 +1,264 @@
+//! JavaScript bindings of the [`pipeline`](crate::pipeline) from PNG or JPEG bytes to
+//! triangles or polygons.
+//!
+//! Points are returned as flat `Float32Array`s of `x, y` pairs and indices as `Uint32Array`s,
+//! since coordinates can go past the range of a `Uint16Array`. Failures are thrown as JavaScript
+//! `Error`s.
+
+use wasm_bindgen::prelude::*;
+
+use crate::geometry::{Face, PointF, Shape};
+use crate::pipeline::{Hitbox, Pipeline, PipelineOptions};
+use crate::triangulation::triangulate_indexed;
+use crate::Error;
+
+/// Rings of several shapes, each shape being its outer ring followed by its holes.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Rings {
+    coordinates: Vec<f32>,
+    ring_lengths: Vec<u32>,
+    hole_counts: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl Rings {
+    /// The `x, y` pairs of every ring, one after the other.
+    #[wasm_bindgen(getter)]
+    pub fn coordinates(&self) -> Vec<f32> {
+        self.coordinates.clone()
+    }
+
+    /// The number of points of each ring.
+    #[wasm_bindgen(getter, js_name = ringLengths)]
+    pub fn ring_lengths(&self) -> Vec<u32> {
+        self.ring_lengths.clone()
+    }
+
+    /// The number of holes of each shape.
+    #[wasm_bindgen(getter, js_name = holeCounts)]
+    pub fn hole_counts(&self) -> Vec<u32> {
+        self.hole_counts.clone()
+    }
+}
+
+/// Triangles of every shape sharing their vertices.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexedTriangles {
+    vertices: Vec<f32>,
+    indices: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl IndexedTriangles {
+    /// The `x, y` pairs of the vertices.
+    #[wasm_bindgen(getter)]
+    pub fn vertices(&self) -> Vec<f32> {
+        self.vertices.clone()
+    }
+
+    /// Three indices into the vertices per triangle.
+    #[wasm_bindgen(getter)]
+    pub fn indices(&self) -> Vec<u32> {
+        self.indices.clone()
+    }
+}
+
+fn flatten(points: impl IntoIterator<Item = PointF>) -> impl Iterator<Item = f32> {
+    points
+        .into_iter()
+        .flat_map(|point| [point.x as f32, point.y as f32])
+}
+
+fn pipeline(alpha_threshold: u8, epsilon: f64, subpixel: bool) -> Pipeline {
+    Pipeline::new(
+        PipelineOptions::new()
+            .alpha_threshold(alpha_threshold)
+            .epsilon(epsilon)
+            .subpixel(subpixel),
+    )
+}
+
+fn decode(bytes: &[u8]) -> Result<image::DynamicImage, Error> {
+    image::load_from_memory(bytes).map_err(Error::Decode)
+}
+
+fn shapes(
+    bytes: &[u8],
+    alpha_threshold: u8,
+    epsilon: f64,
+    subpixel: bool,
+) -> Result<Vec<Shape<PointF>>, Error> {
+    pipeline(alpha_threshold, epsilon, subpixel).shapes(decode(bytes)?)
+}
+
+fn triangles(
+    bytes: &[u8],
+    alpha_threshold: u8,
+    epsilon: f64,
+    subpixel: bool,
+) -> Result<Vec<f32>, Error> {
+    let pipeline = pipeline(alpha_threshold, epsilon, subpixel);
+    let Hitbox::Triangles(triangles) = pipeline.run(decode(bytes)?)? else {
+        unreachable!("the default output is triangles");
+    };
+    Ok(flatten(triangles.into_iter().flat_map(Face::vertices)).collect())
+}
+
+fn indexed_triangles(
+    bytes: &[u8],
+    alpha_threshold: u8,
+    epsilon: f64,
+    subpixel: bool,
+) -> Result<IndexedTriangles, Error> {
+    let pipeline = pipeline(alpha_threshold, epsilon, subpixel);
+    let options = &pipeline.options().triangulation;
+    let mut result = IndexedTriangles::default();
+    for shape in pipeline.shapes(decode(bytes)?)? {
+        let mesh = triangulate_indexed(&shape.outer, Some(&shape.holes), options)?;
+        let offset = (result.vertices.len() / 2) as u32;
+        result.vertices.extend(flatten(mesh.vertices));
+        result
+            .indices
+            .extend(mesh.indices.iter().map(|index| index + offset));
+    }
+    Ok(result)
+}
+
+fn rings(bytes: &[u8], alpha_threshold: u8, epsilon: f64, subpixel: bool) -> Result<Rings, Error> {
+    let mut result = Rings::default();
+    for shape in shapes(bytes, alpha_threshold, epsilon, subpixel)? {
+        result.hole_counts.push(shape.holes.len() as u32);
+        for ring in std::iter::once(&shape.outer).chain(&shape.holes) {
+            result.ring_lengths.push(ring.len() as u32);
+            result.coordinates.extend(flatten(ring.iter().copied()));
+        }
+    }
+    Ok(result)
+}
+
+/// Triangulates the opaque parts of a PNG or JPEG image, six coordinates per triangle.
+///
+/// `epsilon` is the simplification tolerance in pixels, and `subpixel` follows the edges of
+/// the pixels instead of their centers.
+#[wasm_bindgen(js_name = imageToTriangles)]
+pub fn image_to_triangles(
+    bytes: &[u8],
+    alpha_threshold: u8,
+    epsilon: f64,
+    subpixel: bool,
+) -> Result<Vec<f32>, JsError> {
+    Ok(triangles(bytes, alpha_threshold, epsilon, subpixel)?)
+}
+
+/// [`image_to_triangles`] as vertices and indices.
+#[wasm_bindgen(js_name = imageToIndexedTriangles)]
+pub fn image_to_indexed_triangles(
+    bytes: &[u8],
+    alpha_threshold: u8,
+    epsilon: f64,
+    subpixel: bool,
+) -> Result<IndexedTriangles, JsError> {
+    Ok(indexed_triangles(
+        bytes,
+        alpha_threshold,
+        epsilon,
+        subpixel,
+    )?)
+}
+
+/// The simplified outline and holes of the opaque parts of a PNG or JPEG image.
+#[wasm_bindgen(js_name = imageToPolygons)]
+pub fn image_to_polygons(
+    bytes: &[u8],
+    alpha_threshold: u8,
+    epsilon: f64,
+    subpixel: bool,
+) -> Result<Rings, JsError> {
+    Ok(rings(bytes, alpha_threshold, epsilon, subpixel)?)
+}
+
+/// Runs a [`Pipeline`] configured by [`PipelineOptions`] as JSON, missing fields taking their
+/// default value, and returns the [`Hitbox`] as JSON.
+#[wasm_bindgen(js_name = imageToHitbox)]
+pub fn image_to_hitbox(bytes: &[u8], options: &str) -> Result<String, JsError> {
+    let options: PipelineOptions = serde_json::from_str(options)?;
+    let hitbox = Pipeline::new(options).run(decode(bytes)?)?;
+    Ok(serde_json::to_string(&hitbox)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 12x12 PNG with an opaque 8x8 square that has a 2x2 hole in its middle.
+    fn png() -> Vec<u8> {
+        let mut image = image::RgbaImage::new(12, 12);
+        for y in 2..10 {
+            for x in 2..10 {
+                if !(5..7).contains(&x) || !(5..7).contains(&y) {
+                    image.put_pixel(x, y, image::Rgba([0, 0, 0, 255]));
+                }
+            }
+        }
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    fn triangle_area(coordinates: &[f32]) -> f32 {
+        coordinates
+            .chunks_exact(6)
+            .map(|t| ((t[2] - t[0]) * (t[5] - t[1]) - (t[4] - t[0]) * (t[3] - t[1])).abs() / 2.0)
+            .sum()
+    }
+
+    fn ring_area(coordinates: &[f32]) -> f32 {
+        let points: Vec<&[f32]> = coordinates.chunks_exact(2).collect();
+        let doubled: f32 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(p, q)| p[0] * q[1] - q[0] * p[1])
+            .sum();
+        doubled.abs() / 2.0
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let bytes = png();
+
+        let rings = rings(&bytes, 128, 0.5, true).unwrap();
+        assert_eq!(rings.hole_counts, vec![1]);
+        assert_eq!(rings.ring_lengths.len(), 2);
+        let outer_length = 2 * rings.ring_lengths[0] as usize;
+        let (outer, hole) = rings.coordinates.split_at(outer_length);
+        let expected = ring_area(outer) - ring_area(hole);
+
+        let triangles = triangles(&bytes, 128, 0.5, true).unwrap();
+        assert!((triangle_area(&triangles) - expected).abs() < 1e-3);
+
+        let indexed = indexed_triangles(&bytes, 128, 0.5, true).unwrap();
+        assert_eq!(indexed.vertices, rings.coordinates);
+        assert_eq!(indexed.indices.len(), triangles.len() / 2);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            triangles(b"not an image", 128, 1.0, false),
+            Err(Error::Decode(_))
+        ));
+
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4))
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .unwrap();
+        assert!(matches!(
+            rings(bytes.get_ref(), 128, 1.0, false),
+            Err(Error::EmptyMask)
+        ));
+    }
+}